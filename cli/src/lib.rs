@@ -0,0 +1,350 @@
+#![deny(unsafe_code)]
+#![deny(missing_docs)]
+
+//! Command line tool for the AS5600 on Linux I2C.
+//!
+//! The commands run against any [`embedded_hal::i2c::I2c`] bus, so they work the same with
+//! `/dev/i2c-*` and with [`as5600::sim::SimulatedAs5600`].
+
+use std::fmt::{self, Debug, Display};
+use std::io::{self, BufRead, Write};
+
+use as5600::angle::Angle;
+use as5600::burn::{BurnKind, BurnPolicy, BurnReport};
+use as5600::configuration::{
+    FastFilterThreshold, Hysteresis, OutputStage, PowerMode, PwmFreq, SlowFilterMode, WatchdogState,
+};
+use as5600::magnet::SupplyVoltage;
+use as5600::As5600;
+use clap::{Args, Subcommand, ValueEnum};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::I2c;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+#[cfg(test)]
+mod test;
+
+/// Command line tool error.
+#[derive(Debug)]
+pub enum Error {
+    /// Driver error, formatted.
+    Driver(String),
+    /// Reading the confirmation or writing the output failed.
+    Io(io::Error),
+    /// Serializing the output failed.
+    Json(serde_json::Error),
+    /// The burn was not confirmed.
+    Aborted,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Driver(error) => write!(f, "driver error: {error}"),
+            Self::Io(error) => write!(f, "i/o error: {error}"),
+            Self::Json(error) => write!(f, "json error: {error}"),
+            Self::Aborted => write!(f, "aborted"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl<E: Debug> From<as5600::error::Error<E>> for Error {
+    fn from(error: as5600::error::Error<E>) -> Self {
+        Self::Driver(format!("{error:?}"))
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+/// Subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Read raw and scaled angle once.
+    Read,
+    /// Print the angle repeatedly.
+    Watch {
+        /// Time between two reads in milliseconds.
+        #[arg(long, default_value_t = 100)]
+        interval_ms: u32,
+        /// Number of reads, unlimited if not given.
+        #[arg(long)]
+        count: Option<u64>,
+    },
+    /// Get or set the configuration.
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// Set the zero position, by default to the current raw angle.
+    Zero {
+        /// Zero position in counts, 0 to 4095.
+        #[arg(long, value_parser = clap::value_parser!(u16).range(0..4096))]
+        position: Option<u16>,
+    },
+    /// Show magnet status and placement quality.
+    Status {
+        /// Supply voltage, `3v3` or `5v`, which determines the AGC range.
+        #[arg(long, default_value = "5v")]
+        supply: SupplyVoltage,
+    },
+    /// Dump all registers.
+    Dump,
+    /// Burn the current settings to the OTP memory. This is irreversible.
+    Burn {
+        /// What to burn.
+        #[arg(value_enum)]
+        kind: Kind,
+        /// Do not ask for confirmation.
+        #[arg(long)]
+        yes: bool,
+        /// Run all checks, but do not burn.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// `config` subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum ConfigCommand {
+    /// Print the configuration.
+    Get,
+    /// Change individual fields of the configuration.
+    Set(ConfigArgs),
+}
+
+/// Configuration fields to change. Values are the variant names, e.g. `Lsb2`.
+#[derive(Debug, Clone, Default, Args)]
+pub struct ConfigArgs {
+    /// Power mode.
+    #[arg(long, value_parser = variant::<PowerMode>)]
+    pub power_mode: Option<PowerMode>,
+    /// Hysteresis.
+    #[arg(long, value_parser = variant::<Hysteresis>)]
+    pub hysteresis: Option<Hysteresis>,
+    /// Output stage.
+    #[arg(long, value_parser = variant::<OutputStage>)]
+    pub output_stage: Option<OutputStage>,
+    /// PWM frequency.
+    #[arg(long, value_parser = variant::<PwmFreq>)]
+    pub pwm_frequency: Option<PwmFreq>,
+    /// Slow filter mode.
+    #[arg(long, value_parser = variant::<SlowFilterMode>)]
+    pub slow_filter: Option<SlowFilterMode>,
+    /// Fast filter threshold.
+    #[arg(long, value_parser = variant::<FastFilterThreshold>)]
+    pub fast_filter_threshold: Option<FastFilterThreshold>,
+    /// Watchdog state.
+    #[arg(long, value_parser = variant::<WatchdogState>)]
+    pub watchdog_state: Option<WatchdogState>,
+}
+
+/// What to burn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Kind {
+    /// Zero position and maximum position.
+    Angle,
+    /// Maximum angle and configuration.
+    Setting,
+}
+
+impl From<Kind> for BurnKind {
+    fn from(kind: Kind) -> Self {
+        match kind {
+            Kind::Angle => Self::Angle,
+            Kind::Setting => Self::Setting,
+        }
+    }
+}
+
+/// Parse an enum variant by its serialized name.
+fn variant<T: DeserializeOwned>(name: &str) -> Result<T, String> {
+    serde_json::from_value(Value::String(name.to_string())).map_err(|error| error.to_string())
+}
+
+/// Where the output goes and where confirmations come from.
+pub struct Console<W, P, R> {
+    /// Output, e.g. stdout.
+    pub out: W,
+    /// Output for confirmation prompts, e.g. stderr, so they never mix with JSON output.
+    pub prompt: P,
+    /// Input for confirmations.
+    pub input: R,
+    /// Print JSON lines instead of text.
+    pub json: bool,
+}
+
+impl<W: Write, P: Write, R: BufRead> Console<W, P, R> {
+    /// Print a value, as JSON line or as `key: value` lines.
+    fn emit(&mut self, value: &Value) -> Result<(), Error> {
+        if self.json {
+            writeln!(self.out, "{value}")?;
+            return Ok(());
+        }
+        match value {
+            Value::Object(fields) => {
+                for (key, value) in fields {
+                    writeln!(self.out, "{key}: {}", text(value))?;
+                }
+            }
+            value => writeln!(self.out, "{}", text(value))?,
+        }
+        Ok(())
+    }
+
+    /// Ask for confirmation, which requires typing `yes`.
+    fn confirm(&mut self, question: &str) -> Result<(), Error> {
+        write!(self.prompt, "{question} Type 'yes' to continue: ")?;
+        self.prompt.flush()?;
+        let mut answer = String::new();
+        self.input.read_line(&mut answer)?;
+        if answer.trim() == "yes" {
+            Ok(())
+        } else {
+            Err(Error::Aborted)
+        }
+    }
+}
+
+/// Render a JSON value without quotes around strings.
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Run a command.
+pub fn run<I, E, D, W, P, R>(
+    as5600: &mut As5600<I>,
+    command: &Command,
+    console: &mut Console<W, P, R>,
+    delay: &mut D,
+) -> Result<(), Error>
+where
+    I: I2c<Error = E>,
+    E: Debug,
+    D: DelayNs,
+    W: Write,
+    P: Write,
+    R: BufRead,
+{
+    match command {
+        Command::Read => {
+            let raw_angle = as5600.raw_angle()?;
+            let angle = as5600.angle()?;
+            console.emit(&json!({
+                "raw_angle": raw_angle.counts(),
+                "angle": angle.counts(),
+                "degrees": angle.to_degrees(),
+            }))
+        }
+        Command::Watch { interval_ms, count } => {
+            let mut reads = 0;
+            while count.is_none_or(|count| reads < count) {
+                if reads > 0 {
+                    delay.delay_ms(*interval_ms);
+                }
+                let angle = as5600.angle()?;
+                if console.json {
+                    console.emit(&json!({ "angle": angle.counts() }))?;
+                } else {
+                    writeln!(console.out, "{angle:.1}")?;
+                }
+                reads += 1;
+            }
+            Ok(())
+        }
+        Command::Config(ConfigCommand::Get) => {
+            let config = as5600.config()?;
+            console.emit(&serde_json::to_value(config)?)
+        }
+        Command::Config(ConfigCommand::Set(args)) => {
+            let config = as5600.modify_config(|config| {
+                if let Some(power_mode) = args.power_mode {
+                    config.power_mode = power_mode;
+                }
+                if let Some(hysteresis) = args.hysteresis {
+                    config.hysteresis = hysteresis;
+                }
+                if let Some(output_stage) = args.output_stage {
+                    config.output_stage = output_stage;
+                }
+                if let Some(pwm_frequency) = args.pwm_frequency {
+                    config.pwm_frequency = pwm_frequency;
+                }
+                if let Some(slow_filter) = args.slow_filter {
+                    config.slow_filter = slow_filter;
+                }
+                if let Some(fast_filter_threshold) = args.fast_filter_threshold {
+                    config.fast_filter_threshold = fast_filter_threshold;
+                }
+                if let Some(watchdog_state) = args.watchdog_state {
+                    config.watchdog_state = watchdog_state;
+                }
+            })?;
+            console.emit(&serde_json::to_value(config)?)
+        }
+        Command::Zero { position } => {
+            let position = match position {
+                Some(position) => Angle::from_counts(*position),
+                None => as5600.raw_angle()?,
+            };
+            as5600.set_zero_position(position)?;
+            console.emit(&json!({ "zero_position": as5600.zero_position()?.counts() }))
+        }
+        Command::Status { supply } => {
+            let quality = as5600.magnet_quality(*supply)?;
+            console.emit(&json!({
+                "magnet_detected": quality.status.magnet_detected(),
+                "too_weak": quality.status.too_weak(),
+                "too_strong": quality.status.too_strong(),
+                "agc": quality.agc,
+                "magnitude": quality.magnitude,
+                "placement": format!("{:?}", quality.placement()),
+                "score": quality.score(),
+                "advice": quality.advice(),
+            }))
+        }
+        Command::Dump => {
+            let snapshot = as5600.read_all()?;
+            console.emit(&serde_json::to_value(snapshot)?)
+        }
+        Command::Burn { kind, yes, dry_run } => {
+            let kind = BurnKind::from(*kind);
+            if *dry_run {
+                as5600.set_burn_policy(BurnPolicy::DryRun);
+            }
+            let session = as5600.burn_session(kind)?;
+            if !*dry_run && !*yes {
+                let intended = serde_json::to_string(session.intended())?;
+                console.confirm(&format!(
+                    "Burning {kind:?} is irreversible, intended values: {intended}."
+                ))?;
+            }
+            let report = session.execute(delay)?;
+            console.emit(&burn_report(&report)?)
+        }
+    }
+}
+
+fn burn_report(report: &BurnReport) -> Result<Value, Error> {
+    Ok(json!({
+        "kind": format!("{:?}", report.kind),
+        "burned": report.outcome.burned(),
+        "zmco_before": report.zmco_before,
+        "zmco_after": report.zmco_after,
+        "intended": serde_json::to_value(report.intended)?,
+        "reloaded": serde_json::to_value(report.reloaded)?,
+    }))
+}
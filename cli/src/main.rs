@@ -0,0 +1,65 @@
+use std::io;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use as5600::angle::Angle;
+use as5600::constants::{parse_address, DEFAULT_I2C_ADDRESS};
+use as5600::sim::{Motion, SimulatedAs5600};
+use as5600::As5600;
+use as5600_cli::{run, Command, Console, Error};
+use clap::Parser;
+use linux_embedded_hal::{Delay, I2cdev};
+
+/// Read and configure an AS5600 on Linux I2C.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// I2C device.
+    #[arg(long, default_value = "/dev/i2c-1")]
+    device: String,
+    /// I2C address, decimal or hexadecimal with `0x` prefix.
+    #[arg(long, default_value_t = DEFAULT_I2C_ADDRESS, value_parser = parse_address)]
+    address: u8,
+    /// Use a simulated, slowly rotating device instead of the I2C device.
+    #[arg(long)]
+    sim: bool,
+    /// Print JSON lines.
+    #[arg(long)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let mut console = Console {
+        out: io::stdout(),
+        prompt: io::stderr(),
+        input: io::stdin().lock(),
+        json: cli.json,
+    };
+    let result = if cli.sim {
+        let mut sim = SimulatedAs5600::with_address(cli.address);
+        sim.set_tick(Duration::from_millis(1));
+        sim.set_motion(Motion::ConstantSpeed {
+            start: Angle::ZERO,
+            counts_per_second: 1000.0,
+        });
+        let mut as5600 = As5600::with_address(cli.address, sim);
+        run(&mut as5600, &cli.command, &mut console, &mut Delay)
+    } else {
+        I2cdev::new(&cli.device)
+            .map_err(|error| Error::Driver(error.to_string()))
+            .and_then(|i2c| {
+                let mut as5600 = As5600::with_address(cli.address, i2c);
+                run(&mut as5600, &cli.command, &mut console, &mut Delay)
+            })
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use std::io::Cursor;
+
+use as5600::angle::Angle;
+use as5600::configuration::{Hysteresis, PowerMode};
+use as5600::magnet::SupplyVoltage;
+use as5600::sim::SimulatedAs5600;
+use as5600::status::StatusFlags;
+use as5600::As5600;
+use clap::Parser;
+use embedded_hal::delay::DelayNs;
+use serde_json::Value;
+
+use crate::{run, Command, ConfigArgs, ConfigCommand, Console, Error, Kind};
+
+struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// Output of a command run.
+struct Execution {
+    result: Result<(), Error>,
+    out: String,
+    prompt: String,
+    sim: SimulatedAs5600,
+}
+
+/// Run a command against `sim`, answering confirmations with `input`.
+fn execute(sim: SimulatedAs5600, command: Command, json: bool, input: &str) -> Execution {
+    let mut as5600 = As5600::new(sim);
+    let mut console = Console {
+        out: Vec::new(),
+        prompt: Vec::new(),
+        input: Cursor::new(input.as_bytes()),
+        json,
+    };
+    let result = run(&mut as5600, &command, &mut console, &mut NoDelay);
+    Execution {
+        result,
+        out: String::from_utf8(console.out).unwrap(),
+        prompt: String::from_utf8(console.prompt).unwrap(),
+        sim: as5600.release(),
+    }
+}
+
+fn parse(out: &str) -> Vec<Value> {
+    out.lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn reads_angle() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_raw_angle(Angle::from_counts(1024));
+    let run = execute(sim.clone(), Command::Read, true, "");
+    run.result.unwrap();
+    let values = parse(&run.out);
+    assert_eq!(1024, values[0]["raw_angle"]);
+    assert_eq!(90.0, values[0]["degrees"]);
+
+    let run = execute(sim, Command::Read, false, "");
+    run.result.unwrap();
+    assert!(run.out.contains("raw_angle: 1024\n"));
+}
+
+#[test]
+fn watches_count_times() {
+    let command = Command::Watch {
+        interval_ms: 10,
+        count: Some(3),
+    };
+    let run = execute(SimulatedAs5600::new(), command, true, "");
+    run.result.unwrap();
+    assert_eq!(3, parse(&run.out).len());
+}
+
+#[test]
+fn sets_config_fields() {
+    let args = ConfigArgs {
+        power_mode: Some(PowerMode::Lpm2),
+        hysteresis: Some(Hysteresis::Lsb3),
+        ..ConfigArgs::default()
+    };
+    let command = Command::Config(ConfigCommand::Set(args));
+    let run = execute(SimulatedAs5600::new(), command, true, "");
+    run.result.unwrap();
+
+    let command = Command::Config(ConfigCommand::Get);
+    let run = execute(run.sim, command, true, "");
+    run.result.unwrap();
+    let values = parse(&run.out);
+    assert_eq!("Lpm2", values[0]["power_mode"]);
+    assert_eq!("Lsb3", values[0]["hysteresis"]);
+    assert_eq!("Off", values[0]["watchdog_state"]);
+}
+
+#[test]
+fn zeroes_at_current_angle() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_raw_angle(Angle::from_counts(500));
+    let run = execute(sim, Command::Zero { position: None }, false, "");
+    run.result.unwrap();
+    assert_eq!(Angle::from_counts(500), run.sim.zero_position());
+    assert_eq!(Angle::ZERO, run.sim.angle());
+}
+
+#[test]
+fn rejects_zero_position_out_of_range() {
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[command(subcommand)]
+        command: Command,
+    }
+    let cli = Cli::try_parse_from(["as5600", "zero", "--position", "4095"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Command::Zero {
+            position: Some(4095)
+        }
+    ));
+    assert!(Cli::try_parse_from(["as5600", "zero", "--position", "4096"]).is_err());
+}
+
+#[test]
+fn reports_status_and_dump() {
+    let command = Command::Status {
+        supply: SupplyVoltage::V5,
+    };
+    let run = execute(SimulatedAs5600::new(), command, true, "");
+    run.result.unwrap();
+    assert_eq!(true, parse(&run.out)[0]["magnet_detected"]);
+
+    let run = execute(run.sim, Command::Dump, true, "");
+    run.result.unwrap();
+    assert_eq!(0, parse(&run.out)[0]["zmco"]);
+}
+
+#[test]
+fn burn_requires_confirmation() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_raw_angle(Angle::from_counts(100));
+    let run = execute(sim, Command::Zero { position: None }, false, "");
+    run.result.unwrap();
+    let burn = |yes, dry_run| Command::Burn {
+        kind: Kind::Angle,
+        yes,
+        dry_run,
+    };
+
+    let run = execute(run.sim, burn(false, false), true, "no\n");
+    assert!(matches!(run.result, Err(Error::Aborted)));
+    assert!(run.prompt.contains("irreversible"));
+    assert_eq!("", run.out);
+    assert_eq!(0, run.sim.zmco());
+
+    let run = execute(run.sim, burn(false, true), true, "");
+    run.result.unwrap();
+    assert_eq!(false, parse(&run.out)[0]["burned"]);
+    assert_eq!(0, run.sim.zmco());
+
+    let run = execute(run.sim, burn(false, false), true, "yes\n");
+    run.result.unwrap();
+    assert_eq!(true, parse(&run.out)[0]["burned"]);
+    assert_eq!(1, run.sim.zmco());
+    assert_eq!(Angle::from_counts(100), run.sim.otp().zero_position);
+}
+
+#[test]
+fn reports_driver_errors() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_status(StatusFlags::from_bits_retain(0));
+    let command = Command::Burn {
+        kind: Kind::Angle,
+        yes: true,
+        dry_run: false,
+    };
+    let run = execute(sim, command, false, "");
+    assert!(matches!(run.result, Err(Error::Driver(_))));
+    assert_eq!(0, run.sim.zmco());
+}
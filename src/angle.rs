@@ -0,0 +1,221 @@
+use core::f32::consts::TAU;
+use core::fmt;
+use core::ops::{Add, Sub};
+
+#[cfg(test)]
+use proptest_derive::Arbitrary;
+
+/// Angle error.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// Counts above [`Angle::MAX`] (counts given).
+    OutOfRange(u16),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange(counts) => write!(
+                f,
+                "angle of {counts} counts is out of range, expected at most {}",
+                Angle::MAX.counts()
+            ),
+        }
+    }
+}
+
+/// Angle in 12-bit sensor counts (0..4096 per full turn).
+///
+/// This is the unit of the `RAW ANGLE`, `ANGLE`, `ZPOS`, `MPOS` and `MANG` registers.
+/// Arithmetic wraps around modulo one full turn.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(test, derive(Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u16", into = "u16")
+)]
+pub struct Angle(#[cfg_attr(test, proptest(strategy = "0u16..Angle::COUNTS_PER_TURN"))] u16);
+
+impl Angle {
+    /// Number of counts in one full turn.
+    pub const COUNTS_PER_TURN: u16 = 4096;
+
+    /// Largest representable angle (one count short of a full turn).
+    pub const MAX: Self = Self(Self::COUNTS_PER_TURN - 1);
+
+    /// Zero angle.
+    pub const ZERO: Self = Self(0);
+
+    /// Create an angle from 12-bit counts, discarding the upper 4 bits.
+    pub const fn from_counts(counts: u16) -> Self {
+        Self(counts & (Self::COUNTS_PER_TURN - 1))
+    }
+
+    /// Get the 12-bit counts of this angle.
+    pub const fn counts(self) -> u16 {
+        self.0
+    }
+
+    /// Convert to degrees in `[0, 360)`.
+    ///
+    /// One count is exactly 45/512 degrees, so the result is exact in `f32`.
+    pub fn to_degrees(self) -> f32 {
+        f32::from(self.0) * 360.0 / f32::from(Self::COUNTS_PER_TURN)
+    }
+
+    /// Convert to radians in `[0, 2π)`.
+    pub fn to_radians(self) -> f32 {
+        self.to_turns() * TAU
+    }
+
+    /// Convert to a fraction of a full turn in `[0, 1)`, exact in `f32`.
+    pub fn to_turns(self) -> f32 {
+        f32::from(self.0) / f32::from(Self::COUNTS_PER_TURN)
+    }
+
+    /// Convert to degrees as unsigned Q16.16 fixed-point (lossless).
+    pub const fn to_degrees_fixed(self) -> u32 {
+        // 360 * 2^16 / 4096 = 5760.
+        self.0 as u32 * 5760
+    }
+
+    /// Convert to turns as unsigned Q0.16 fixed-point (lossless).
+    pub const fn to_turns_fixed(self) -> u16 {
+        self.0 << 4
+    }
+
+    /// Add two angles, wrapping around at a full turn.
+    pub const fn wrapping_add(self, rhs: Self) -> Self {
+        Self::from_counts(self.0.wrapping_add(rhs.0))
+    }
+
+    /// Subtract two angles, wrapping around at a full turn.
+    pub const fn wrapping_sub(self, rhs: Self) -> Self {
+        Self::from_counts(self.0.wrapping_sub(rhs.0))
+    }
+
+    /// Signed difference `self - other` along the shortest path, in counts.
+    ///
+    /// The result lies in `-2048..=2047`; a difference of exactly half a turn is reported as negative.
+    pub const fn shortest_difference(self, other: Self) -> i16 {
+        let diff = self.wrapping_sub(other).0 as i16;
+        if diff >= (Self::COUNTS_PER_TURN / 2) as i16 {
+            diff - Self::COUNTS_PER_TURN as i16
+        } else {
+            diff
+        }
+    }
+}
+
+impl TryFrom<u16> for Angle {
+    type Error = Error;
+
+    /// Unlike [`Angle::from_counts`], counts above [`Angle::MAX`] are rejected.
+    fn try_from(counts: u16) -> Result<Self, Self::Error> {
+        if counts < Self::COUNTS_PER_TURN {
+            Ok(Self(counts))
+        } else {
+            Err(Error::OutOfRange(counts))
+        }
+    }
+}
+
+impl From<Angle> for u16 {
+    fn from(angle: Angle) -> Self {
+        angle.0
+    }
+}
+
+impl Add for Angle {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.wrapping_add(rhs)
+    }
+}
+
+impl Sub for Angle {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.wrapping_sub(rhs)
+    }
+}
+
+impl fmt::Display for Angle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*}°", precision, self.to_degrees()),
+            None => write!(f, "{}°", self.to_degrees()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::angle::{Angle, Error};
+    use proptest::prelude::*;
+
+    #[test]
+    fn converts_to_units() {
+        let quarter = Angle::from_counts(1024);
+        assert_eq!(90.0, quarter.to_degrees());
+        assert_eq!(0.25, quarter.to_turns());
+        assert_eq!(core::f32::consts::FRAC_PI_2, quarter.to_radians());
+        assert_eq!(90 << 16, quarter.to_degrees_fixed());
+        assert_eq!(0x4000, quarter.to_turns_fixed());
+        assert_eq!("90°", format!("{quarter}"));
+        assert_eq!("0.09°", format!("{:.2}", Angle::from_counts(1)));
+    }
+
+    #[test]
+    fn masks_upper_bits() {
+        assert_eq!(Angle::from_counts(0x0123), Angle::from_counts(0xF123));
+    }
+
+    #[test]
+    fn conversion_rejects_upper_bits() {
+        assert_eq!(Ok(Angle::MAX), Angle::try_from(4095));
+        assert_eq!(Err(Error::OutOfRange(4096)), Angle::try_from(4096));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserializing_rejects_upper_bits() {
+        assert_eq!(Angle::MAX, serde_json::from_str::<Angle>("4095").unwrap());
+        assert!(serde_json::from_str::<Angle>("5000").is_err());
+    }
+
+    #[test]
+    fn differences_take_shortest_path() {
+        let a = Angle::from_counts(4090);
+        let b = Angle::from_counts(5);
+        assert_eq!(11, b.shortest_difference(a));
+        assert_eq!(-11, a.shortest_difference(b));
+        assert_eq!(Angle::from_counts(11), b - a);
+        assert_eq!(Angle::from_counts(3), a + Angle::from_counts(9));
+        assert_eq!(
+            -2048,
+            Angle::ZERO.shortest_difference(Angle::from_counts(2048))
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn shortest_difference_roundtrip(a in any::<Angle>(), b in any::<Angle>()) {
+            let diff = a.shortest_difference(b);
+            assert!((-2048..=2047).contains(&diff));
+            let back = Angle::from_counts((i32::from(b.counts()) + i32::from(diff)) as u16);
+            assert_eq!(a, back);
+        }
+
+        #[test]
+        fn fixed_point_is_lossless(a in any::<Angle>()) {
+            assert_eq!(a.to_degrees(), a.to_degrees_fixed() as f32 / 65536.0);
+            assert_eq!(a.counts(), a.to_turns_fixed() >> 4);
+        }
+    }
+}
@@ -1,23 +1,60 @@
 use embedded_hal::delay::DelayNs;
 use embedded_hal::i2c::I2c as BlockingI2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::{delay::DelayNs as AsyncDelayNs, i2c::I2c as AsyncI2c};
 
-use crate::configuration::Configuration;
+use crate::angle::Angle;
+use crate::burn::{
+    self, BurnContents, BurnKind, BurnOutcome, BurnPolicy, BurnReport, OtpSnapshot,
+    LOAD_OTP_COMMANDS,
+};
+use crate::configuration::{
+    Configuration, FastFilterThreshold, Hysteresis, OutputStage, PowerMode, PwmFreq,
+    RawConfiguration, SlowFilterMode, WatchdogState,
+};
 use crate::constants::DEFAULT_I2C_ADDRESS;
 use crate::error::Error;
+use crate::magnet::{MagnetQuality, SupplyVoltage};
+use crate::profile::{Profile, ProfileReport};
 use crate::register::Register;
-use crate::status::Status;
+use crate::snapshot::{RegisterSnapshot, Telemetry};
+use crate::status::{Status, StatusFlags};
 
 /// As5600 driver instance.
+#[maybe_async_cfg::maybe(
+    idents(
+        As5600(sync),
+        AsyncDelayNs(sync = "DelayNs", async),
+        AsyncI2c(sync = "BlockingI2c", async),
+        BurnSession(sync)
+    ),
+    sync(),
+    async(feature = "async")
+)]
 #[derive(Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct As5600<I2C> {
     address: u8,
     bus: I2C,
+    burn_policy: BurnPolicy,
+    streaming: bool,
+    /// Register the device address pointer is known to hold, see [`Register::holds_pointer`].
+    pointer: Option<Register>,
 }
 
+#[maybe_async_cfg::maybe(
+    idents(
+        As5600(sync),
+        AsyncDelayNs(sync = "DelayNs", async),
+        AsyncI2c(sync = "BlockingI2c", async),
+        BurnSession(sync)
+    ),
+    sync(),
+    async(feature = "async")
+)]
 impl<I, E> As5600<I>
 where
-    I: BlockingI2c<Error = E>,
+    I: AsyncI2c<Error = E>,
 {
     /// Create a new As5600 driver instance.
     pub fn new(bus: I) -> Self {
@@ -26,7 +63,38 @@ where
 
     /// Create a new As5600 driver instance.
     pub fn with_address(address: u8, bus: I) -> Self {
-        Self { address, bus }
+        Self {
+            address,
+            bus,
+            burn_policy: BurnPolicy::Allow,
+            streaming: false,
+            pointer: None,
+        }
+    }
+
+    /// Set the policy applied to commands which burn the OTP memory.
+    pub fn set_burn_policy(&mut self, policy: BurnPolicy) {
+        self.burn_policy = policy;
+    }
+
+    /// Get the policy applied to commands which burn the OTP memory.
+    pub fn burn_policy(&self) -> BurnPolicy {
+        self.burn_policy
+    }
+
+    /// Enable or disable streaming reads.
+    ///
+    /// After reading `RAW_ANGLE`, `ANGLE` or `MAGNITUDE`, the device address pointer stays on
+    /// that register. In streaming mode, repeated reads of the same register then skip sending
+    /// the register address. Any other access re-primes the pointer on the next read.
+    /// Only enable this if no other driver or bus master addresses the device in between.
+    pub fn set_streaming(&mut self, enabled: bool) {
+        self.streaming = enabled;
+    }
+
+    /// Whether streaming reads are enabled.
+    pub fn streaming(&self) -> bool {
+        self.streaming
     }
 
     /// Release the bus, consuming the driver.
@@ -35,142 +103,246 @@ where
     }
 
     /// Get value of register `RAW_ANGLE`.
-    pub fn raw_angle(&mut self) -> Result<u16, Error<E>> {
+    pub async fn raw_angle(&mut self) -> Result<Angle, Error<E>> {
         // 12-bit value.
-        Ok(self.read_u16(Register::RawAngle)? & 0x0FFF)
+        Ok(Angle::from_counts(self.read_u16(Register::RawAngle).await?))
     }
 
     /// Get value of register `ANGLE`.
-    pub fn angle(&mut self) -> Result<u16, Error<E>> {
+    pub async fn angle(&mut self) -> Result<Angle, Error<E>> {
         // 12-bit value.
-        Ok(self.read_u16(Register::Angle)? & 0x0FFF)
+        Ok(Angle::from_counts(self.read_u16(Register::Angle).await?))
     }
 
     /// Get value of register `ZMCO`.
-    pub fn zmco(&mut self) -> Result<u8, Error<E>> {
+    pub async fn zmco(&mut self) -> Result<u8, Error<E>> {
         let mut buffer = [0u8; 1];
-        self.bus
-            .write_read(self.address, &[Register::Zmco.into()], &mut buffer)
-            .map_err(Error::Communication)?;
+        self.read_registers(Register::Zmco, &mut buffer).await?;
         Ok(buffer[0] & 0b0000_0011)
     }
 
     /// Get value of register `STATUS`.
-    pub fn magnet_status(&mut self) -> Result<Status, Error<E>> {
+    pub async fn magnet_status(&mut self) -> Result<Status, Error<E>> {
+        self.magnet_status_flags()
+            .await?
+            .status()
+            .map_err(Error::Status)
+    }
+
+    /// Get value of register `STATUS` as individual flags, preserving the raw byte.
+    pub async fn magnet_status_flags(&mut self) -> Result<StatusFlags, Error<E>> {
         let mut buffer = [0u8; 1];
-        self.bus
-            .write_read(self.address, &[Register::Status.into()], &mut buffer)
-            .map_err(Error::Communication)?;
-        Status::try_from(buffer).map_err(Error::Status)
+        self.read_registers(Register::Status, &mut buffer).await?;
+        Ok(StatusFlags::from(buffer))
     }
 
     /// Get value of register `ZPOS`.
-    pub fn zero_position(&mut self) -> Result<u16, Error<E>> {
+    pub async fn zero_position(&mut self) -> Result<Angle, Error<E>> {
         // 12-bit value.
-        Ok(self.read_u16(Register::Zpos)? & 0x0FFF)
+        Ok(Angle::from_counts(self.read_u16(Register::Zpos).await?))
     }
 
     /// Set value of register `ZPOS`.
-    pub fn set_zero_position(&mut self, bytes: u16) -> Result<(), Error<E>> {
-        // 12-bit value.
-        self.write_u16(Register::Zpos, bytes & 0x0FFF)
+    pub async fn set_zero_position(&mut self, position: Angle) -> Result<(), Error<E>> {
+        self.write_u16(Register::Zpos, position.counts()).await
     }
 
     /// Get value of register `MPOS`.
-    pub fn maximum_position(&mut self) -> Result<u16, Error<E>> {
+    pub async fn maximum_position(&mut self) -> Result<Angle, Error<E>> {
         // 12-bit value.
-        Ok(self.read_u16(Register::Mpos)? & 0x0FFF)
+        Ok(Angle::from_counts(self.read_u16(Register::Mpos).await?))
     }
 
     /// Set value of register `MPOS`.
-    pub fn set_maximum_position(&mut self, bytes: u16) -> Result<(), Error<E>> {
-        // 12-bit value.
-        self.write_u16(Register::Mpos, bytes & 0x0FFF)
+    pub async fn set_maximum_position(&mut self, position: Angle) -> Result<(), Error<E>> {
+        self.write_u16(Register::Mpos, position.counts()).await
     }
 
     /// Get value of register `MANG`.
-    pub fn maximum_angle(&mut self) -> Result<u16, Error<E>> {
+    pub async fn maximum_angle(&mut self) -> Result<Angle, Error<E>> {
         // 12-bit value.
-        Ok(self.read_u16(Register::Mang)? & 0x0FFF)
+        Ok(Angle::from_counts(self.read_u16(Register::Mang).await?))
     }
 
     /// Set value of register `MANG`.
-    pub fn set_maximum_angle(&mut self, bytes: u16) -> Result<(), Error<E>> {
-        // 12-bit value.
-        self.write_u16(Register::Mang, bytes & 0x0FFF)
+    pub async fn set_maximum_angle(&mut self, angle: Angle) -> Result<(), Error<E>> {
+        self.write_u16(Register::Mang, angle.counts()).await
     }
 
     /// Get value of register `CONF` and parse it.
-    pub fn config(&mut self) -> Result<Configuration, Error<E>> {
-        let bytes = self.read_u16(Register::Conf)?;
+    pub async fn config(&mut self) -> Result<Configuration, Error<E>> {
+        let bytes = self.read_u16(Register::Conf).await?;
         Configuration::try_from(bytes).map_err(Error::Configuration)
     }
 
+    /// Get value of register `CONF` and parse it leniently, see [`RawConfiguration`].
+    pub async fn raw_config(&mut self) -> Result<RawConfiguration, Error<E>> {
+        Ok(RawConfiguration::from(self.read_u16(Register::Conf).await?))
+    }
+
     /// Set value of register `CONF`.
-    pub fn set_config(&mut self, config: Configuration) -> Result<(), Error<E>> {
-        // See note in datasheet about "blank fields may contain factory settings" on page 18.
-        let current_config = self.read_u16(Register::Conf)?;
-        let blank_fields = current_config & 0b1100_0000_0000_0000;
+    ///
+    /// A configuration read from the device carries its blank fields and is written at once.
+    /// Otherwise the register is read first to preserve them, see
+    /// [`Configuration::blank_fields`].
+    pub async fn set_config(&mut self, config: Configuration) -> Result<(), Error<E>> {
         let mut bytes = u16::from(config);
-        bytes |= blank_fields;
-        self.write_u16(Register::Conf, bytes)
+        if config.blank_fields().is_none() {
+            // See note in datasheet about "blank fields may contain factory settings" on page 18.
+            let current_config = self.read_u16(Register::Conf).await?;
+            bytes |= current_config & Configuration::BLANK_FIELDS;
+        }
+        self.write_u16(Register::Conf, bytes).await
+    }
+
+    /// Set the power mode in register `CONF` with a single read-modify-write.
+    pub async fn set_power_mode(
+        &mut self,
+        power_mode: PowerMode,
+    ) -> Result<Configuration, Error<E>> {
+        self.modify_config(|config| config.power_mode = power_mode)
+            .await
+    }
+
+    /// Set the hysteresis in register `CONF` with a single read-modify-write.
+    pub async fn set_hysteresis(
+        &mut self,
+        hysteresis: Hysteresis,
+    ) -> Result<Configuration, Error<E>> {
+        self.modify_config(|config| config.hysteresis = hysteresis)
+            .await
+    }
+
+    /// Set the output stage in register `CONF` with a single read-modify-write.
+    pub async fn set_output_stage(
+        &mut self,
+        output_stage: OutputStage,
+    ) -> Result<Configuration, Error<E>> {
+        self.modify_config(|config| config.output_stage = output_stage)
+            .await
+    }
+
+    /// Set the PWM frequency in register `CONF` with a single read-modify-write.
+    pub async fn set_pwm_frequency(
+        &mut self,
+        pwm_frequency: PwmFreq,
+    ) -> Result<Configuration, Error<E>> {
+        self.modify_config(|config| config.pwm_frequency = pwm_frequency)
+            .await
+    }
+
+    /// Set the slow filter mode in register `CONF` with a single read-modify-write.
+    pub async fn set_slow_filter(
+        &mut self,
+        slow_filter: SlowFilterMode,
+    ) -> Result<Configuration, Error<E>> {
+        self.modify_config(|config| config.slow_filter = slow_filter)
+            .await
+    }
+
+    /// Set the fast filter threshold in register `CONF` with a single read-modify-write.
+    pub async fn set_fast_filter_threshold(
+        &mut self,
+        fast_filter_threshold: FastFilterThreshold,
+    ) -> Result<Configuration, Error<E>> {
+        self.modify_config(|config| config.fast_filter_threshold = fast_filter_threshold)
+            .await
+    }
+
+    /// Set the watchdog state in register `CONF` with a single read-modify-write.
+    pub async fn set_watchdog(&mut self, state: WatchdogState) -> Result<Configuration, Error<E>> {
+        self.modify_config(|config| config.watchdog_state = state)
+            .await
+    }
+
+    /// Read register `CONF`, apply `modify` and write it back, returning the written value.
+    ///
+    /// Changes several fields with a single read-modify-write.
+    pub async fn modify_config<F>(&mut self, modify: F) -> Result<Configuration, Error<E>>
+    where
+        F: FnOnce(&mut Configuration),
+    {
+        let mut config = self.config().await?;
+        modify(&mut config);
+        self.set_config(config).await?;
+        Ok(config)
     }
 
     /// Get value of register `AGC`.
-    /// This value differs depending on the supply voltage (5V or 3v3), see datasheet.
-    pub fn automatic_gain_control(&mut self) -> Result<u8, Error<E>> {
+    /// This value differs depending on the supply voltage (5V or 3v3), see datasheet:
+    /// the range is 0..=255 in 5V operation and 0..=128 in 3.3V operation.
+    pub async fn automatic_gain_control(&mut self) -> Result<u8, Error<E>> {
         let mut buffer = [0u8; 1];
-        self.bus
-            .write_read(self.address, &[0x1a], &mut buffer)
-            .map_err(Error::Communication)?;
+        self.read_registers(Register::Agc, &mut buffer).await?;
         Ok(buffer[0])
     }
 
     /// Get value of register `MAGNITUDE`.
-    pub fn magnitude(&mut self) -> Result<u16, Error<E>> {
+    pub async fn magnitude(&mut self) -> Result<u16, Error<E>> {
         // 12-bit value.
-        Ok(self.read_u16(Register::Magnitude)? & 0x0FFF)
+        Ok(self.read_u16(Register::Magnitude).await? & 0x0FFF)
+    }
+
+    /// Assess the magnet placement from registers `STATUS`, `AGC` and `MAGNITUDE`.
+    ///
+    /// Uses the same two burst reads as [`As5600::read_telemetry`].
+    pub async fn magnet_quality(
+        &mut self,
+        supply: SupplyVoltage,
+    ) -> Result<MagnetQuality, Error<E>> {
+        let telemetry = self.read_telemetry().await?;
+        Ok(MagnetQuality::new(
+            telemetry.status,
+            telemetry.automatic_gain_control,
+            telemetry.magnitude,
+            supply,
+        ))
     }
 
     /// Burn maximum angle and config register.
     /// Only proceeds if position settings (MPOS and ZPOS) have never been persisted before.
     /// See datasheet for constraints.
-    pub fn persist_maximum_angle_and_config_settings<D>(
+    /// Subject to the [`BurnPolicy`], see [`As5600::set_burn_policy`].
+    pub async fn persist_maximum_angle_and_config_settings<D>(
         &mut self,
         delay: &mut D,
-    ) -> Result<(), Error<E>>
+    ) -> Result<BurnOutcome, Error<E>>
     where
-        D: DelayNs,
+        D: AsyncDelayNs,
     {
-        let zmco = self.zmco()?;
+        let zmco = self.zmco().await?;
         if zmco != 0 {
             return Err(Error::MangConfigPersistenceExhausted);
         }
-        self.bus
-            .write(self.address, &[Register::Burn.into(), 0x40])
-            .map_err(Error::Communication)?;
-        delay.delay_ms(1);
-        Ok(())
+        let outcome = self.burn(BurnKind::Setting, None).await?;
+        if outcome.burned() {
+            delay.delay_ms(1).await;
+        }
+        Ok(outcome)
     }
 
     /// Burn zero position and maximum to As5600 memory, if ZMCO permits it and a magnet is detected.
     /// See datasheet for constraints.
-    pub fn persist_position_settings<D>(&mut self, delay: &mut D) -> Result<(), Error<E>>
+    /// Subject to the [`BurnPolicy`], see [`As5600::set_burn_policy`].
+    pub async fn persist_position_settings<D>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<BurnOutcome, Error<E>>
     where
-        D: DelayNs,
+        D: AsyncDelayNs,
     {
-        let zmco = self.zmco()?;
+        let zmco = self.zmco().await?;
         if zmco >= 3 {
             return Err(Error::MaximumPositionPersistsReached);
         }
-        if self.magnet_status()? != Status::MagnetDetected {
+        if self.magnet_status().await? != Status::MagnetDetected {
             return Err(Error::MagnetRequired);
         }
-        self.bus
-            .write(self.address, &[Register::Burn.into(), 0x80])
-            .map_err(Error::Communication)?;
-        delay.delay_ms(1);
-        Ok(())
+        let outcome = self.burn(BurnKind::Angle, None).await?;
+        if outcome.burned() {
+            delay.delay_ms(1).await;
+        }
+        Ok(outcome)
     }
 
     /// Set the I2C address.
@@ -179,23 +351,18 @@ where
     /// The address must be between 8 and 119 (0x08 and 0x77).
     /// The address is automatically shifted left by 1 bit before writing to the device.
     #[cfg(feature = "as5600l")]
-    pub fn set_address(&mut self, address: u8) -> Result<(), Error<E>> {
+    pub async fn set_address(&mut self, address: u8) -> Result<(), Error<E>> {
         // Skip reserved I2C addresses (same validation as C implementation)
-        if address < 8 || address > 119 {
+        if !(8..=119).contains(&address) {
             return Err(Error::InvalidAddress);
         }
 
         // Note: address needs to be shifted 1 bit (same as C implementation)
         let shifted_address = address << 1;
-        self.bus
-            .write(
-                self.address,
-                &[Register::I2CAddress.into(), shifted_address],
-            )
-            .map_err(Error::Communication)?;
-        self.bus
-            .write(self.address, &[Register::I2CUPDT.into(), shifted_address])
-            .map_err(Error::Communication)?;
+        self.write(&[Register::I2CAddress.into(), shifted_address])
+            .await?;
+        self.write(&[Register::I2CUPDT.into(), shifted_address])
+            .await?;
         self.address = address;
         Ok(())
     }
@@ -203,29 +370,300 @@ where
     /// Burn the current address to the AS5600L memory.
     ///
     /// This function is only available for AS5600L devices.
+    /// Subject to the [`BurnPolicy`], see [`As5600::set_burn_policy`].
     #[cfg(feature = "as5600l")]
-    pub fn persist_address(&mut self) -> Result<(), Error<E>> {
-        self.bus
-            .write(self.address, &[Register::Burn.into(), 0x40])
-            .map_err(Error::Communication)?;
+    pub async fn persist_address(&mut self) -> Result<BurnOutcome, Error<E>> {
+        let intended = match self.burn_policy {
+            BurnPolicy::DryRun => {
+                let mut buffer = [0u8; 1];
+                self.read_registers(Register::I2CAddress, &mut buffer)
+                    .await?;
+                Some(BurnContents::Address {
+                    address: buffer[0] >> 1,
+                })
+            }
+            BurnPolicy::Allow | BurnPolicy::Deny => None,
+        };
+        self.burn(BurnKind::Setting, intended).await
+    }
+
+    /// Start a guided burn of the current shadow register contents.
+    /// See [`BurnSession`] for the procedure.
+    pub async fn burn_session(&mut self, kind: BurnKind) -> Result<BurnSession<'_, I>, Error<E>> {
+        BurnSession::new(self, kind).await
+    }
+
+    /// Load the OTP contents into the shadow registers and read them back.
+    ///
+    /// This overwrites any unburned values in registers `ZPOS`, `MPOS`, `MANG` and `CONF`.
+    pub async fn reload_otp(&mut self) -> Result<OtpSnapshot, Error<E>> {
+        for command in LOAD_OTP_COMMANDS {
+            self.command(command).await?;
+        }
+        self.otp_snapshot().await
+    }
+
+    /// Write the profile settings, verify them by read-back and, if requested, burn them.
+    ///
+    /// The range register the profile leaves out is cleared, as a stale maximum position
+    /// would take precedence over the maximum angle.
+    ///
+    /// Burning first burns maximum angle and configuration, then zero and maximum position.
+    /// Both burns are verified and subject to the [`BurnPolicy`].
+    pub async fn apply_profile<D>(
+        &mut self,
+        profile: &Profile,
+        delay: &mut D,
+    ) -> Result<ProfileReport, Error<E>>
+    where
+        D: AsyncDelayNs,
+    {
+        profile.validate().map_err(Error::Profile)?;
+        self.write_positions(profile).await?;
+        self.set_maximum_angle(profile.maximum_angle.unwrap_or(Angle::ZERO))
+            .await?;
+        self.set_config(profile.config).await?;
+        let readback = self.otp_snapshot().await?;
+        profile.verify(&readback).map_err(Error::Profile)?;
+        let mut report = ProfileReport {
+            readback,
+            settings_burn: None,
+            angle_burn: None,
+        };
+        if profile.burn {
+            let session = self.burn_session(BurnKind::Setting).await?;
+            report.settings_burn = Some(session.execute(delay).await?);
+            // Reloading the OTP after burning restores the unburned positions.
+            self.write_positions(profile).await?;
+            let session = self.burn_session(BurnKind::Angle).await?;
+            report.angle_burn = Some(session.execute(delay).await?);
+        }
+        Ok(report)
+    }
+
+    /// Write zero and, if given, maximum position of the profile.
+    async fn write_positions(&mut self, profile: &Profile) -> Result<(), Error<E>> {
+        self.set_zero_position(profile.zero_position).await?;
+        self.set_maximum_position(profile.maximum_position.unwrap_or(Angle::ZERO))
+            .await
+    }
+
+    /// Read registers `ZPOS`, `MPOS`, `MANG` and `CONF`.
+    pub(crate) async fn otp_snapshot(&mut self) -> Result<OtpSnapshot, Error<E>> {
+        Ok(OtpSnapshot {
+            zero_position: self.zero_position().await?,
+            maximum_position: self.maximum_position().await?,
+            maximum_angle: self.maximum_angle().await?,
+            config: self.config().await?,
+        })
+    }
+
+    /// Read the registers a burn of the given kind makes permanent, parsing `CONF` leniently.
+    async fn burn_contents(&mut self, kind: BurnKind) -> Result<BurnContents, Error<E>> {
+        Ok(match kind {
+            BurnKind::Angle => BurnContents::Angle {
+                zero_position: self.zero_position().await?,
+                maximum_position: self.maximum_position().await?,
+            },
+            BurnKind::Setting => BurnContents::Setting {
+                maximum_angle: self.maximum_angle().await?,
+                config: self.raw_config().await?,
+            },
+        })
+    }
+
+    /// Write a burn command to register `BURN`, subject to the burn policy.
+    ///
+    /// A dry run reports `intended`, or reads the shadow registers if it is not given.
+    pub(crate) async fn burn(
+        &mut self,
+        kind: BurnKind,
+        intended: Option<BurnContents>,
+    ) -> Result<BurnOutcome, Error<E>> {
+        let command = kind.command();
+        match self.burn_policy {
+            BurnPolicy::Allow => {
+                self.command(command).await?;
+                Ok(BurnOutcome::Burned(command))
+            }
+            BurnPolicy::Deny => Err(Error::BurnDenied),
+            BurnPolicy::DryRun => {
+                let intended = match intended {
+                    Some(intended) => intended,
+                    None => self.burn_contents(kind).await?,
+                };
+                Ok(BurnOutcome::DryRun { command, intended })
+            }
+        }
+    }
+
+    /// Write a command to register `BURN`.
+    async fn command(&mut self, command: u8) -> Result<(), Error<E>> {
+        self.write(&[Register::Burn.into(), command]).await
+    }
+
+    /// Read all registers from `ZMCO` through `MAGNITUDE` in three burst reads.
+    pub async fn read_all(&mut self) -> Result<RegisterSnapshot, Error<E>> {
+        let mut settings = [0u8; 9];
+        self.read_registers(Register::Zmco, &mut settings).await?;
+        let mut output = [0u8; 5];
+        self.read_registers(Register::Status, &mut output).await?;
+        let mut magnet = [0u8; 3];
+        self.read_registers(Register::Agc, &mut magnet).await?;
+        Ok(RegisterSnapshot::from_bytes(settings, output, magnet))
+    }
+
+    /// Read registers `STATUS`, `RAW_ANGLE`, `ANGLE`, `AGC` and `MAGNITUDE` in two burst reads.
+    pub async fn read_telemetry(&mut self) -> Result<Telemetry, Error<E>> {
+        let mut output = [0u8; 5];
+        self.read_registers(Register::Status, &mut output).await?;
+        let mut magnet = [0u8; 3];
+        self.read_registers(Register::Agc, &mut magnet).await?;
+        Ok(Telemetry::from_bytes(output, magnet))
+    }
+
+    /// Read consecutive bytes starting at the given register, using the auto-incrementing
+    /// address pointer.
+    ///
+    /// Registers which hold the address pointer (see [`Register::holds_pointer`]) can only
+    /// start a burst of at most 2 bytes.
+    /// With streaming enabled, see [`As5600::set_streaming`], a 2 byte read of the register the
+    /// address pointer stays on skips sending the register address.
+    pub async fn read_registers(
+        &mut self,
+        start: Register,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<E>> {
+        if start.holds_pointer() && buffer.len() > 2 {
+            return Err(Error::InvalidBurstRead(start));
+        }
+        let primed = self.streaming && buffer.len() == 2 && self.pointer == Some(start);
+        // Unknown until the transfer succeeds.
+        self.pointer = None;
+        if primed {
+            self.bus
+                .read(self.address, buffer)
+                .await
+                .map_err(Error::Communication)?;
+        } else {
+            self.bus
+                .write_read(self.address, &[start.into()], buffer)
+                .await
+                .map_err(Error::Communication)?;
+        }
+        // After both bytes, the pointer is back at the high byte.
+        if start.holds_pointer() && buffer.len() == 2 {
+            self.pointer = Some(start);
+        }
         Ok(())
     }
 
     /// Helper function for write-reading 2 bytes from the given register.
-    fn read_u16(&mut self, command: Register) -> Result<u16, Error<E>> {
+    async fn read_u16(&mut self, command: Register) -> Result<u16, Error<E>> {
         let mut buffer = [0u8; 2];
-        self.bus
-            .write_read(self.address, &[command.into()], &mut buffer)
-            .map_err(Error::Communication)?;
+        self.read_registers(command, &mut buffer).await?;
         Ok(u16::from_be_bytes(buffer))
     }
 
+    /// Helper function for writing, which moves the address pointer.
+    async fn write(&mut self, bytes: &[u8]) -> Result<(), Error<E>> {
+        self.pointer = None;
+        self.bus
+            .write(self.address, bytes)
+            .await
+            .map_err(Error::Communication)
+    }
+
     /// Helper function for writing 2 bytes to the given register.
-    fn write_u16(&mut self, command: Register, bytes: u16) -> Result<(), Error<E>> {
+    async fn write_u16(&mut self, command: Register, bytes: u16) -> Result<(), Error<E>> {
         let bytes: [u8; 2] = bytes.to_be_bytes();
         let buffer = [u8::from(command), bytes[0], bytes[1]];
-        self.bus
-            .write(self.address, &buffer)
-            .map_err(Error::Communication)
+        self.write(&buffer).await
+    }
+}
+
+/// Guided OTP burn following the datasheet procedure.
+///
+/// 1. Write the intended values to the shadow registers.
+/// 2. [`BurnSession::new`] snapshots them and checks the datasheet constraints.
+/// 3. [`BurnSession::execute`] burns, reloads the OTP into the shadow registers and verifies them.
+///
+/// With [`BurnPolicy::DryRun`], step 3 neither burns nor reloads the OTP.
+#[maybe_async_cfg::maybe(
+    idents(
+        As5600(sync),
+        AsyncDelayNs(sync = "DelayNs", async),
+        AsyncI2c(sync = "BlockingI2c", async),
+        BurnSession(sync)
+    ),
+    sync(),
+    async(feature = "async")
+)]
+#[derive(Debug, PartialEq, Eq)]
+pub struct BurnSession<'a, I2C> {
+    driver: &'a mut As5600<I2C>,
+    kind: BurnKind,
+    zmco: u8,
+    intended: OtpSnapshot,
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        As5600(sync),
+        AsyncDelayNs(sync = "DelayNs", async),
+        AsyncI2c(sync = "BlockingI2c", async),
+        BurnSession(sync)
+    ),
+    sync(),
+    async(feature = "async")
+)]
+impl<'a, I, E> BurnSession<'a, I>
+where
+    I: AsyncI2c<Error = E>,
+{
+    /// Snapshot the shadow registers and run the pre-flight checks.
+    pub async fn new(driver: &'a mut As5600<I>, kind: BurnKind) -> Result<Self, Error<E>> {
+        let zmco = driver.zmco().await?;
+        let status = driver.magnet_status_flags().await?;
+        let intended = driver.otp_snapshot().await?;
+        burn::preflight(kind, zmco, status, &intended)?;
+        Ok(Self {
+            driver,
+            kind,
+            zmco,
+            intended,
+        })
+    }
+
+    /// Get the values which are going to be burned.
+    pub fn intended(&self) -> &OtpSnapshot {
+        &self.intended
+    }
+
+    /// Burn, reload the OTP and verify the result.
+    pub async fn execute<D>(self, delay: &mut D) -> Result<BurnReport, Error<E>>
+    where
+        D: AsyncDelayNs,
+    {
+        let outcome = self
+            .driver
+            .burn(self.kind, Some(self.intended.burn_contents(self.kind)))
+            .await?;
+        let reloaded = if outcome.burned() {
+            delay.delay_ms(1).await;
+            Some(self.driver.reload_otp().await?)
+        } else {
+            None
+        };
+        let report = BurnReport {
+            kind: self.kind,
+            outcome,
+            zmco_before: self.zmco,
+            zmco_after: self.driver.zmco().await?,
+            intended: self.intended,
+            reloaded,
+        };
+        burn::verify(&report).map_err(Error::Burn)?;
+        Ok(report)
     }
 }
@@ -0,0 +1,385 @@
+use crate::angle::Angle;
+use crate::configuration::{Configuration, RawConfiguration};
+use crate::snapshot::Field;
+use crate::status::StatusFlags;
+
+pub use crate::as5600::BurnSession;
+
+/// Smallest angular range the datasheet allows for `MANG` or `MPOS - ZPOS`: 18 degrees.
+pub const MINIMUM_RANGE: Angle = Angle::from_counts(205);
+
+/// `BURN` command sequence which loads the OTP contents into the shadow registers.
+pub(crate) const LOAD_OTP_COMMANDS: [u8; 3] = [0x01, 0x11, 0x10];
+
+/// Burn verification/validation error.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// The angular range is below [`MINIMUM_RANGE`] (counts given).
+    RangeTooSmall(u16),
+    /// A register read back after reloading the OTP does not hold the burned value.
+    Mismatch {
+        /// Mismatching register.
+        field: Field,
+        /// Value which was supposed to be burned.
+        expected: u16,
+        /// Value read back after reloading the OTP.
+        actual: u16,
+    },
+}
+
+/// What to burn.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BurnKind {
+    /// `BURN_ANGLE`: zero position and maximum position, at most 3 times.
+    Angle,
+    /// `BURN_SETTING`: maximum angle and configuration, once and only before any angle burn.
+    Setting,
+}
+
+impl BurnKind {
+    /// Value written to register `BURN`.
+    pub const fn command(&self) -> u8 {
+        match self {
+            Self::Angle => 0x80,
+            Self::Setting => 0x40,
+        }
+    }
+}
+
+/// Policy applied to commands which burn the OTP memory.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BurnPolicy {
+    /// Burn commands are written to the device.
+    #[default]
+    Allow,
+    /// Burn commands fail with [`crate::error::Error::BurnDenied`] after the pre-checks.
+    Deny,
+    /// Burn commands run all pre-checks, but are not written to the device.
+    /// The outcome reports the register contents which would have been burned, see
+    /// [`BurnContents`].
+    DryRun,
+}
+
+/// Result of a burn command which passed all pre-checks.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BurnOutcome {
+    /// The command was written to register `BURN`.
+    Burned(u8),
+    /// The command would have been written to register `BURN`, see [`BurnPolicy::DryRun`].
+    DryRun {
+        /// Value which would have been written to register `BURN`.
+        command: u8,
+        /// Shadow register contents which would have been made permanent.
+        intended: BurnContents,
+    },
+}
+
+/// Shadow register contents made permanent by a burn command.
+///
+/// Registers are read as is, so a dry run does not fail on contents a real burn would accept.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BurnContents {
+    /// `BURN_ANGLE`: zero position and maximum position.
+    Angle {
+        /// Zero position.
+        zero_position: Angle,
+        /// Maximum position.
+        maximum_position: Angle,
+    },
+    /// `BURN_SETTING`: maximum angle and configuration, including its blank fields.
+    Setting {
+        /// Maximum angle.
+        maximum_angle: Angle,
+        /// Configuration, parsed leniently.
+        config: RawConfiguration,
+    },
+    /// `BURN_SETTING` issued to persist the I2C address of an AS5600L.
+    Address {
+        /// 7-bit address in register `I2CADDR`.
+        address: u8,
+    },
+}
+
+impl BurnOutcome {
+    /// Value (which would have been) written to register `BURN`.
+    pub const fn command(&self) -> u8 {
+        match self {
+            Self::Burned(command) | Self::DryRun { command, .. } => *command,
+        }
+    }
+
+    /// Whether the command was actually written.
+    pub const fn burned(&self) -> bool {
+        matches!(self, Self::Burned(_))
+    }
+}
+
+/// Contents of the programmable registers `ZPOS`, `MPOS`, `MANG` and `CONF`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OtpSnapshot {
+    /// Zero position.
+    pub zero_position: Angle,
+    /// Maximum position.
+    pub maximum_position: Angle,
+    /// Maximum angle.
+    pub maximum_angle: Angle,
+    /// Configuration.
+    pub config: Configuration,
+}
+
+impl OtpSnapshot {
+    /// Contents made permanent by a burn of the given kind.
+    pub(crate) fn burn_contents(&self, kind: BurnKind) -> BurnContents {
+        match kind {
+            BurnKind::Angle => BurnContents::Angle {
+                zero_position: self.zero_position,
+                maximum_position: self.maximum_position,
+            },
+            BurnKind::Setting => BurnContents::Setting {
+                maximum_angle: self.maximum_angle,
+                config: RawConfiguration::from(u16::from(self.config)),
+            },
+        }
+    }
+
+    /// Values of the registers written by a burn of the given kind.
+    fn burned_fields(&self, kind: BurnKind) -> [(Field, u16); 2] {
+        match kind {
+            BurnKind::Angle => [
+                (Field::ZeroPosition, self.zero_position.counts()),
+                (Field::MaximumPosition, self.maximum_position.counts()),
+            ],
+            BurnKind::Setting => [
+                (Field::MaximumAngle, self.maximum_angle.counts()),
+                (Field::Config, u16::from(self.config)),
+            ],
+        }
+    }
+}
+
+/// Result of a successful, verified burn.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BurnReport {
+    /// What was burned.
+    pub kind: BurnKind,
+    /// Whether the burn command was written or only dry-run.
+    pub outcome: BurnOutcome,
+    /// `ZMCO` before burning.
+    pub zmco_before: u8,
+    /// `ZMCO` after burning.
+    pub zmco_after: u8,
+    /// Register contents before burning.
+    pub intended: OtpSnapshot,
+    /// Register contents after reloading the OTP, `None` for a dry run.
+    pub reloaded: Option<OtpSnapshot>,
+}
+
+/// Check the angular range against [`MINIMUM_RANGE`], returning the range in counts if it is
+/// too small.
+///
+/// The range is `MPOS - ZPOS`, or `MANG` if the maximum position is zero. As on the device, a
+/// maximum position of zero is unused and a maximum angle of zero means the full circle.
+pub(crate) fn check_range(
+    zero_position: Angle,
+    maximum_position: Angle,
+    maximum_angle: Angle,
+) -> Result<(), u16> {
+    let range = if maximum_position != Angle::ZERO {
+        maximum_position - zero_position
+    } else if maximum_angle != Angle::ZERO {
+        maximum_angle
+    } else {
+        return Ok(());
+    };
+    if range < MINIMUM_RANGE {
+        Err(range.counts())
+    } else {
+        Ok(())
+    }
+}
+
+/// Check the datasheet constraints before burning.
+pub(crate) fn preflight<E>(
+    kind: BurnKind,
+    zmco: u8,
+    status: StatusFlags,
+    intended: &OtpSnapshot,
+) -> Result<(), crate::error::Error<E>> {
+    use crate::error::Error as DriverError;
+    match kind {
+        BurnKind::Angle => {
+            if zmco >= 3 {
+                return Err(DriverError::MaximumPositionPersistsReached);
+            }
+            if !status.magnet_detected() {
+                return Err(DriverError::MagnetRequired);
+            }
+            check_range(
+                intended.zero_position,
+                intended.maximum_position,
+                Angle::ZERO,
+            )
+            .map_err(|range| DriverError::Burn(Error::RangeTooSmall(range)))?;
+        }
+        BurnKind::Setting => {
+            if zmco != 0 {
+                return Err(DriverError::MangConfigPersistenceExhausted);
+            }
+            check_range(intended.zero_position, Angle::ZERO, intended.maximum_angle)
+                .map_err(|range| DriverError::Burn(Error::RangeTooSmall(range)))?;
+        }
+    }
+    Ok(())
+}
+
+/// Compare the reloaded registers with the intended values.
+pub(crate) fn verify(report: &BurnReport) -> Result<(), Error> {
+    let Some(reloaded) = report.reloaded else {
+        return Ok(());
+    };
+    if report.kind == BurnKind::Angle && report.zmco_after != report.zmco_before + 1 {
+        return Err(Error::Mismatch {
+            field: Field::Zmco,
+            expected: u16::from(report.zmco_before + 1),
+            actual: u16::from(report.zmco_after),
+        });
+    }
+    let expected = report.intended.burned_fields(report.kind);
+    let actual = reloaded.burned_fields(report.kind);
+    for ((field, expected), (_, actual)) in expected.into_iter().zip(actual) {
+        if expected != actual {
+            return Err(Error::Mismatch {
+                field,
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+
+    use crate::angle::Angle;
+    use crate::burn::{BurnContents, BurnKind, BurnOutcome, BurnPolicy, BurnSession, Error};
+    use crate::configuration::RawConfiguration;
+    use crate::error;
+    use crate::sim::SimulatedAs5600;
+    use crate::snapshot::Field;
+    use crate::As5600;
+
+    #[test]
+    fn burns_and_verifies_angle() {
+        let mut as5600 = As5600::new(SimulatedAs5600::new());
+        as5600.set_zero_position(Angle::from_counts(100)).unwrap();
+        as5600
+            .set_maximum_position(Angle::from_counts(1100))
+            .unwrap();
+        let session = BurnSession::new(&mut as5600, BurnKind::Angle).unwrap();
+        assert_eq!(Angle::from_counts(100), session.intended().zero_position);
+        let report = session.execute(&mut NoopDelay).unwrap();
+        assert_eq!((0, 1), (report.zmco_before, report.zmco_after));
+        assert_eq!(Some(report.intended), report.reloaded);
+        assert_eq!(
+            Angle::from_counts(1100),
+            as5600.release().otp().maximum_position
+        );
+    }
+
+    #[test]
+    fn rejects_small_ranges() {
+        let mut as5600 = As5600::new(SimulatedAs5600::new());
+        as5600.set_maximum_angle(Angle::from_counts(100)).unwrap();
+        assert_eq!(
+            Err(error::Error::Burn(Error::RangeTooSmall(100))),
+            BurnSession::new(&mut as5600, BurnKind::Setting).map(|_| ())
+        );
+        as5600.set_zero_position(Angle::from_counts(4000)).unwrap();
+        as5600
+            .set_maximum_position(Angle::from_counts(100))
+            .unwrap();
+        assert_eq!(
+            Err(error::Error::Burn(Error::RangeTooSmall(196))),
+            BurnSession::new(&mut as5600, BurnKind::Angle).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn detects_mismatch_after_reload() {
+        let mut as5600 = As5600::new(SimulatedAs5600::new());
+        as5600.set_maximum_angle(Angle::from_counts(1000)).unwrap();
+        as5600
+            .persist_maximum_angle_and_config_settings(&mut NoopDelay)
+            .unwrap();
+        // The device ignores a second settings burn, which the read-back must reveal.
+        as5600.set_maximum_angle(Angle::from_counts(2000)).unwrap();
+        let session = as5600.burn_session(BurnKind::Setting).unwrap();
+        assert_eq!(
+            Err(error::Error::Burn(Error::Mismatch {
+                field: Field::MaximumAngle,
+                expected: 2000,
+                actual: 1000
+            })),
+            session.execute(&mut NoopDelay)
+        );
+    }
+
+    #[test]
+    fn dry_run_leaves_otp_untouched() {
+        let mut as5600 = As5600::new(SimulatedAs5600::new());
+        as5600.set_burn_policy(BurnPolicy::DryRun);
+        as5600.set_maximum_angle(Angle::from_counts(1000)).unwrap();
+        let report = as5600
+            .burn_session(BurnKind::Setting)
+            .unwrap()
+            .execute(&mut NoopDelay)
+            .unwrap();
+        assert_eq!(
+            BurnOutcome::DryRun {
+                command: 0x40,
+                intended: BurnContents::Setting {
+                    maximum_angle: Angle::from_counts(1000),
+                    config: RawConfiguration::from(0),
+                }
+            },
+            report.outcome
+        );
+        assert_eq!(Angle::from_counts(1000), report.intended.maximum_angle);
+        assert_eq!((0, 0), (report.zmco_before, report.zmco_after));
+        assert_eq!(None, report.reloaded);
+        assert_eq!(Angle::from_counts(1000), as5600.maximum_angle().unwrap());
+        assert_eq!(Angle::ZERO, as5600.release().otp().maximum_angle);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_position_settings() {
+        use crate::burn::OtpSnapshot;
+        use crate::configuration::Configuration;
+        use crate::status::Status;
+
+        let snapshot = OtpSnapshot {
+            zero_position: Angle::from_counts(100),
+            maximum_position: Angle::from_counts(1100),
+            maximum_angle: Angle::ZERO,
+            config: Configuration::low_noise(),
+        };
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert!(json.starts_with(r#"{"zero_position":100,"maximum_position":1100,"#));
+        assert_eq!(snapshot, serde_json::from_str(&json).unwrap());
+        assert_eq!(
+            r#""MagnetDetected""#,
+            serde_json::to_string(&Status::MagnetDetected).unwrap()
+        );
+    }
+}
@@ -3,7 +3,7 @@ use num_traits::FromPrimitive;
 
 use super::{
     Configuration, FastFilterThreshold, Hysteresis, OutputStage, PowerMode, PwmFreq,
-    SlowFilterMode, WatchdogState,
+    RawConfiguration, SlowFilterMode, WatchdogState,
 };
 
 impl TryFrom<u8> for PowerMode {
@@ -104,9 +104,8 @@ impl From<WatchdogState> for u8 {
     }
 }
 
-impl TryFrom<u16> for Configuration {
-    type Error = Error;
-    fn try_from(bytes: u16) -> Result<Self, Self::Error> {
+impl From<u16> for RawConfiguration {
+    fn from(bytes: u16) -> Self {
         let pm = (bytes & 0b0000_0000_0000_0011) as u8;
         let hyst = ((bytes & 0b0000_0000_0000_1100) >> 2) as u8;
         let outs = ((bytes & 0b0000_0000_0011_0000) >> 4) as u8;
@@ -114,15 +113,23 @@ impl TryFrom<u16> for Configuration {
         let sf = ((bytes & 0b0000_0011_0000_0000) >> 8) as u8;
         let fth = ((bytes & 0b0001_1100_0000_0000) >> 10) as u8;
         let wd = ((bytes & 0b0010_0000_0000_0000) >> 13) as u8;
-        Ok(Self {
-            power_mode: pm.try_into()?,
-            hysteresis: hyst.try_into()?,
-            output_stage: outs.try_into()?,
-            pwm_frequency: pwmf.try_into()?,
-            slow_filter: sf.try_into()?,
-            fast_filter_threshold: fth.try_into()?,
-            watchdog_state: wd.try_into()?,
-        })
+        Self {
+            bits: bytes,
+            power_mode: pm.try_into(),
+            hysteresis: hyst.try_into(),
+            output_stage: outs.try_into(),
+            pwm_frequency: pwmf.try_into(),
+            slow_filter: sf.try_into(),
+            fast_filter_threshold: fth.try_into(),
+            watchdog_state: wd.try_into(),
+        }
+    }
+}
+
+impl TryFrom<u16> for Configuration {
+    type Error = Error;
+    fn try_from(bytes: u16) -> Result<Self, Self::Error> {
+        RawConfiguration::from(bytes).configuration()
     }
 }
 
@@ -143,6 +150,7 @@ impl From<Configuration> for u16 {
         fields |= fth_bits;
         let wd_bits = (u8::from(config.watchdog_state) as Self) << 13;
         fields |= wd_bits;
+        fields |= config.blank_fields.unwrap_or(0);
         fields
     }
 }
@@ -1,7 +1,15 @@
+use core::time::Duration;
+
 use num_derive::FromPrimitive;
 #[cfg(test)]
 use proptest_derive::Arbitrary;
 
+use crate::constants::{
+    OUTPUT_NOISE_1, OUTPUT_NOISE_2, OUTPUT_NOISE_3, OUTPUT_NOISE_4, POLLING_TIME_LPM1,
+    POLLING_TIME_LPM2, POLLING_TIME_LPM3, SAMPLE_RATE, SETTLING_TIME_1, SETTLING_TIME_2,
+    SETTLING_TIME_3, SETTLING_TIME_4,
+};
+
 mod conversion;
 /// Errors
 pub mod error;
@@ -11,6 +19,7 @@ mod test;
 /// Power mode.
 #[derive(Debug, PartialEq, Eq, Copy, Clone, FromPrimitive)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(test, derive(Arbitrary))]
 #[repr(u8)]
 pub enum PowerMode {
@@ -24,9 +33,23 @@ pub enum PowerMode {
     Lpm3 = 0b11,
 }
 
+impl PowerMode {
+    /// Get the interval between two measurements in this power mode.
+    /// In normal power mode, the device samples continuously at [`SAMPLE_RATE`].
+    pub const fn polling_interval(&self) -> Duration {
+        match self {
+            Self::Nom => SAMPLE_RATE,
+            Self::Lpm1 => POLLING_TIME_LPM1,
+            Self::Lpm2 => POLLING_TIME_LPM2,
+            Self::Lpm3 => POLLING_TIME_LPM3,
+        }
+    }
+}
+
 /// Hysteresis mode.
 #[derive(Debug, PartialEq, Eq, Copy, Clone, FromPrimitive)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(test, derive(Arbitrary))]
 #[repr(u8)]
 pub enum Hysteresis {
@@ -44,6 +67,7 @@ pub enum Hysteresis {
 /// Apart from digital i2c output, analog modes or PWM could be generated by the module.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(test, derive(Arbitrary))]
 #[repr(u8)]
 pub enum OutputStage {
@@ -58,6 +82,7 @@ pub enum OutputStage {
 /// PWM frequency.
 #[derive(Debug, PartialEq, Eq, Copy, Clone, FromPrimitive)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(test, derive(Arbitrary))]
 #[repr(u8)]
 pub enum PwmFreq {
@@ -86,6 +111,7 @@ impl PwmFreq {
 /// Slow filter mode.
 #[derive(Debug, PartialEq, Eq, Copy, Clone, FromPrimitive)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(test, derive(Arbitrary))]
 #[repr(u8)]
 pub enum SlowFilterMode {
@@ -99,9 +125,32 @@ pub enum SlowFilterMode {
     X2 = 0b11,
 }
 
+impl SlowFilterMode {
+    /// Get the step response settling time of the slow filter.
+    pub const fn settling_time(&self) -> Duration {
+        match self {
+            Self::X16 => SETTLING_TIME_1,
+            Self::X8 => SETTLING_TIME_2,
+            Self::X4 => SETTLING_TIME_3,
+            Self::X2 => SETTLING_TIME_4,
+        }
+    }
+
+    /// Get the RMS output noise in degrees.
+    pub const fn rms_noise(&self) -> f32 {
+        match self {
+            Self::X16 => OUTPUT_NOISE_1,
+            Self::X8 => OUTPUT_NOISE_2,
+            Self::X4 => OUTPUT_NOISE_3,
+            Self::X2 => OUTPUT_NOISE_4,
+        }
+    }
+}
+
 /// Fast filter threshold.
 #[derive(Debug, PartialEq, Eq, Copy, Clone, FromPrimitive)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(test, derive(Arbitrary))]
 #[repr(u8)]
 pub enum FastFilterThreshold {
@@ -126,6 +175,7 @@ pub enum FastFilterThreshold {
 /// Watchdog state.
 #[derive(Debug, PartialEq, Eq, Copy, Clone, FromPrimitive)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(test, derive(Arbitrary))]
 #[repr(u8)]
 pub enum WatchdogState {
@@ -136,8 +186,11 @@ pub enum WatchdogState {
 }
 
 /// As5600 configuration.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///
+/// Equality compares the settings only, not the blank fields carried from the device.
+#[derive(Debug, Copy, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(test, derive(Arbitrary))]
 pub struct Configuration {
     /// Power mode.
@@ -154,4 +207,251 @@ pub struct Configuration {
     pub fast_filter_threshold: FastFilterThreshold,
     /// Watchdog state.
     pub watchdog_state: WatchdogState,
+    /// Blank fields (bits 14 and 15, in place) as read from the device, see
+    /// [`Configuration::blank_fields`]. Not serialized, as they are specific to one device.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(
+        test,
+        proptest(
+            strategy = "proptest::option::of(proptest::strategy::Strategy::prop_map(0u16..4, |bits| bits << 14))"
+        )
+    )]
+    blank_fields: Option<u16>,
+}
+
+impl PartialEq for Configuration {
+    fn eq(&self, other: &Self) -> bool {
+        self.power_mode == other.power_mode
+            && self.hysteresis == other.hysteresis
+            && self.output_stage == other.output_stage
+            && self.pwm_frequency == other.pwm_frequency
+            && self.slow_filter == other.slow_filter
+            && self.fast_filter_threshold == other.fast_filter_threshold
+            && self.watchdog_state == other.watchdog_state
+    }
+}
+
+impl Eq for Configuration {}
+
+/// Leniently parsed register `CONF`, see [`RawConfiguration::from`].
+///
+/// Every field is decoded on its own, so an invalid bit pattern in one field does not hide
+/// the others. Useful for diagnostics of misbehaving devices.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RawConfiguration {
+    /// Register value as read.
+    pub bits: u16,
+    /// Power mode.
+    pub power_mode: Result<PowerMode, error::Error>,
+    /// Hysteresis.
+    pub hysteresis: Result<Hysteresis, error::Error>,
+    /// Output stage.
+    pub output_stage: Result<OutputStage, error::Error>,
+    /// PWM frequency.
+    pub pwm_frequency: Result<PwmFreq, error::Error>,
+    /// Slow filter mode.
+    pub slow_filter: Result<SlowFilterMode, error::Error>,
+    /// Fast filter threshold.
+    pub fast_filter_threshold: Result<FastFilterThreshold, error::Error>,
+    /// Watchdog state.
+    pub watchdog_state: Result<WatchdogState, error::Error>,
+}
+
+impl RawConfiguration {
+    /// Whether every field holds a valid bit pattern.
+    pub const fn is_valid(&self) -> bool {
+        self.power_mode.is_ok()
+            && self.hysteresis.is_ok()
+            && self.output_stage.is_ok()
+            && self.pwm_frequency.is_ok()
+            && self.slow_filter.is_ok()
+            && self.fast_filter_threshold.is_ok()
+            && self.watchdog_state.is_ok()
+    }
+
+    /// Strict configuration, or the error of the first invalid field.
+    pub fn configuration(&self) -> Result<Configuration, error::Error> {
+        Ok(Configuration {
+            power_mode: self.power_mode?,
+            hysteresis: self.hysteresis?,
+            output_stage: self.output_stage?,
+            pwm_frequency: self.pwm_frequency?,
+            slow_filter: self.slow_filter?,
+            fast_filter_threshold: self.fast_filter_threshold?,
+            watchdog_state: self.watchdog_state?,
+            blank_fields: Some(self.bits & Configuration::BLANK_FIELDS),
+        })
+    }
+}
+
+impl Configuration {
+    /// Mask of the blank fields (bits 14 and 15) in register `CONF`.
+    pub const BLANK_FIELDS: u16 = 0b1100_0000_0000_0000;
+
+    /// Blank fields (bits 14 and 15, in place) of register `CONF`, if this configuration was
+    /// read from the device.
+    ///
+    /// The datasheet notes that blank fields may contain factory settings. Writing a
+    /// configuration which carries them needs no extra read to preserve them.
+    pub const fn blank_fields(&self) -> Option<u16> {
+        self.blank_fields
+    }
+
+    /// Get the interval between two measurements, see [`PowerMode::polling_interval`].
+    pub const fn polling_interval(&self) -> Duration {
+        self.power_mode.polling_interval()
+    }
+
+    /// Get the step response settling time, see [`SlowFilterMode::settling_time`].
+    ///
+    /// With a fast filter threshold set, steps above the threshold settle faster.
+    pub const fn settling_time(&self) -> Duration {
+        self.slow_filter.settling_time()
+    }
+
+    /// Get the RMS output noise in degrees, see [`SlowFilterMode::rms_noise`].
+    pub const fn rms_noise(&self) -> f32 {
+        self.slow_filter.rms_noise()
+    }
+
+    /// Create a builder, starting from the power-on configuration (all bits zero).
+    pub const fn builder() -> ConfigurationBuilder {
+        ConfigurationBuilder::new()
+    }
+
+    /// Fastest response: 2x slow filter and fast filter at 6 LSB, no hysteresis.
+    ///
+    /// Settling time [`SETTLING_TIME_4`] (0.286ms), with more output noise.
+    pub const fn low_latency() -> Self {
+        Self::builder()
+            .slow_filter(SlowFilterMode::X2)
+            .fast_filter_threshold(FastFilterThreshold::Lsb6)
+            .build()
+    }
+
+    /// Lowest output noise: 16x slow filter without fast filter, 1 LSB hysteresis.
+    ///
+    /// Settling time [`SETTLING_TIME_1`] (2.2ms).
+    pub const fn low_noise() -> Self {
+        Self::builder()
+            .hysteresis(Hysteresis::Lsb1)
+            .slow_filter(SlowFilterMode::X16)
+            .fast_filter_threshold(FastFilterThreshold::SlowFilterOnly)
+            .build()
+    }
+
+    /// Lowest current consumption: low power mode 3 (100ms polling) with watchdog.
+    ///
+    /// Settling time [`SETTLING_TIME_1`] (2.2ms) after each polling interval. The watchdog
+    /// additionally enters low power mode 3 after [`crate::constants::WATCHDOG_TIMEOUT`] without motion.
+    pub const fn low_power() -> Self {
+        Self::builder()
+            .power_mode(PowerMode::Lpm3)
+            .hysteresis(Hysteresis::Lsb1)
+            .watchdog_state(WatchdogState::On)
+            .build()
+    }
+
+    /// PWM output at the given frequency, otherwise power-on settings.
+    ///
+    /// Settling time [`SETTLING_TIME_1`] (2.2ms), plus up to one PWM period.
+    pub const fn pwm_output(frequency: PwmFreq) -> Self {
+        Self::builder()
+            .output_stage(OutputStage::DigitalPwm)
+            .pwm_frequency(frequency)
+            .build()
+    }
+}
+
+impl Default for Configuration {
+    /// Reset state of register `CONF` according to the datasheet: all bits zero.
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// Builder for [`Configuration`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ConfigurationBuilder {
+    config: Configuration,
+}
+
+impl ConfigurationBuilder {
+    /// Create a builder, starting from the power-on configuration (all bits zero).
+    pub const fn new() -> Self {
+        Self {
+            config: Configuration {
+                power_mode: PowerMode::Nom,
+                hysteresis: Hysteresis::Off,
+                output_stage: OutputStage::Analog,
+                pwm_frequency: PwmFreq::PwmF1,
+                slow_filter: SlowFilterMode::X16,
+                fast_filter_threshold: FastFilterThreshold::SlowFilterOnly,
+                watchdog_state: WatchdogState::Off,
+                blank_fields: None,
+            },
+        }
+    }
+
+    /// Set the power mode.
+    pub const fn power_mode(mut self, power_mode: PowerMode) -> Self {
+        self.config.power_mode = power_mode;
+        self
+    }
+
+    /// Set the hysteresis.
+    pub const fn hysteresis(mut self, hysteresis: Hysteresis) -> Self {
+        self.config.hysteresis = hysteresis;
+        self
+    }
+
+    /// Set the output stage.
+    pub const fn output_stage(mut self, output_stage: OutputStage) -> Self {
+        self.config.output_stage = output_stage;
+        self
+    }
+
+    /// Set the PWM frequency.
+    pub const fn pwm_frequency(mut self, pwm_frequency: PwmFreq) -> Self {
+        self.config.pwm_frequency = pwm_frequency;
+        self
+    }
+
+    /// Set the slow filter mode.
+    pub const fn slow_filter(mut self, slow_filter: SlowFilterMode) -> Self {
+        self.config.slow_filter = slow_filter;
+        self
+    }
+
+    /// Set the fast filter threshold.
+    pub const fn fast_filter_threshold(mut self, threshold: FastFilterThreshold) -> Self {
+        self.config.fast_filter_threshold = threshold;
+        self
+    }
+
+    /// Set the watchdog state.
+    pub const fn watchdog_state(mut self, watchdog_state: WatchdogState) -> Self {
+        self.config.watchdog_state = watchdog_state;
+        self
+    }
+
+    /// Finish building.
+    pub const fn build(self) -> Configuration {
+        self.config
+    }
+}
+
+impl Default for ConfigurationBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Configuration> for ConfigurationBuilder {
+    /// Start building from an existing configuration.
+    fn from(config: Configuration) -> Self {
+        Self { config }
+    }
 }
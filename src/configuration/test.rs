@@ -1,20 +1,150 @@
-use crate::configuration::Configuration;
+use crate::configuration::error::Error;
+use crate::configuration::{
+    Configuration, ConfigurationBuilder, FastFilterThreshold, Hysteresis, OutputStage, PowerMode,
+    PwmFreq, RawConfiguration, SlowFilterMode, WatchdogState,
+};
+use crate::constants::{
+    OUTPUT_NOISE_1, OUTPUT_NOISE_4, POLLING_TIME_LPM2, SAMPLE_RATE, SETTLING_TIME_1,
+};
+use core::time::Duration;
 use proptest::prelude::*;
 
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(10000))]
     #[test]
     fn bytes_to_config_roundtrip(bytes in any::<u16>()) {
-        let top_most_mask = 0b0011_1111_1111_1111;
         if let Ok(config) = Configuration::try_from(bytes) {
-            let original = u16::from(config);
-            assert_eq!(bytes & top_most_mask, original & top_most_mask);
+            assert_eq!(bytes, u16::from(config));
         }
     }
 
+    #[test]
+    fn lenient_parse_agrees_with_strict_parse(bytes in any::<u16>()) {
+        let raw = RawConfiguration::from(bytes);
+        assert_eq!(raw.is_valid(), raw.configuration().is_ok());
+        assert_eq!(Configuration::try_from(bytes), raw.configuration());
+    }
+
     #[test]
     fn config_to_bytes_roundtrip(config in any::<Configuration>()) {
         let bytes = u16::from(config);
         assert_eq!(config, Configuration::try_from(bytes).unwrap());
     }
 }
+
+#[test]
+fn keeps_blank_fields() {
+    let config = Configuration::try_from(0b1000_0000_0000_0001).unwrap();
+    assert_eq!(Some(0b1000_0000_0000_0000), config.blank_fields());
+    assert_eq!(0b1000_0000_0000_0001, u16::from(config));
+    let built = Configuration::builder().build();
+    assert_eq!(None, built.blank_fields());
+    assert_eq!(0, u16::from(built));
+    // Equality compares the settings only.
+    assert_eq!(Configuration::try_from(0b1100_0000_0000_0000), Ok(built));
+}
+
+#[test]
+fn builder_starts_at_power_on_configuration() {
+    assert_eq!(
+        Ok(Configuration::builder().build()),
+        Configuration::try_from(0)
+    );
+}
+
+#[test]
+fn builder_sets_fields() {
+    let config = Configuration::builder()
+        .power_mode(PowerMode::Lpm2)
+        .hysteresis(Hysteresis::Lsb1)
+        .output_stage(OutputStage::DigitalPwm)
+        .pwm_frequency(PwmFreq::PwmF4)
+        .slow_filter(SlowFilterMode::X4)
+        .fast_filter_threshold(FastFilterThreshold::Lsb9)
+        .watchdog_state(WatchdogState::On)
+        .build();
+    assert_eq!(0b0010_1110_1110_0110, u16::from(config));
+    let changed = ConfigurationBuilder::from(config)
+        .hysteresis(Hysteresis::Off)
+        .build();
+    assert_eq!(
+        Configuration {
+            hysteresis: Hysteresis::Off,
+            ..config
+        },
+        changed
+    );
+}
+
+#[test]
+fn default_is_reset_state() {
+    assert_eq!(0, u16::from(Configuration::default()));
+    assert_eq!(Configuration::builder().build(), Configuration::default());
+}
+
+#[test]
+fn presets() {
+    let low_latency = Configuration::low_latency();
+    assert_eq!(SlowFilterMode::X2, low_latency.slow_filter);
+    assert_eq!(FastFilterThreshold::Lsb6, low_latency.fast_filter_threshold);
+    assert_eq!(SlowFilterMode::X16, Configuration::low_noise().slow_filter);
+    assert_eq!(PowerMode::Lpm3, Configuration::low_power().power_mode);
+    let pwm = Configuration::pwm_output(PwmFreq::PwmF3);
+    assert_eq!(OutputStage::DigitalPwm, pwm.output_stage);
+    assert_eq!(PwmFreq::PwmF3, pwm.pwm_frequency);
+    assert_eq!(
+        Configuration::default(),
+        Configuration {
+            output_stage: OutputStage::Analog,
+            pwm_frequency: PwmFreq::PwmF1,
+            ..pwm
+        }
+    );
+}
+
+#[test]
+fn timing_model() {
+    let config = Configuration::default();
+    assert_eq!(SAMPLE_RATE, config.polling_interval());
+    assert_eq!(SETTLING_TIME_1, config.settling_time());
+    assert_eq!(OUTPUT_NOISE_1, config.rms_noise());
+    let config = Configuration::builder()
+        .power_mode(PowerMode::Lpm2)
+        .slow_filter(SlowFilterMode::X2)
+        .build();
+    assert_eq!(POLLING_TIME_LPM2, config.polling_interval());
+    assert_eq!(Duration::from_micros(286), config.settling_time());
+    assert_eq!(OUTPUT_NOISE_4, config.rms_noise());
+    assert_eq!(
+        Duration::from_millis(100),
+        PowerMode::Lpm3.polling_interval()
+    );
+}
+
+#[test]
+fn lenient_parse_keeps_valid_fields() {
+    let raw = RawConfiguration::from(0b0010_0011_0011_0100);
+    assert!(!raw.is_valid());
+    assert_eq!(Err(Error::OutputStageBitPattern(0b11)), raw.output_stage);
+    assert_eq!(Ok(Hysteresis::Lsb1), raw.hysteresis);
+    assert_eq!(Ok(SlowFilterMode::X2), raw.slow_filter);
+    assert_eq!(Ok(WatchdogState::On), raw.watchdog_state);
+    assert_eq!(Err(Error::OutputStageBitPattern(0b11)), raw.configuration());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serializes_with_variant_names() {
+    let config = Configuration::pwm_output(PwmFreq::PwmF2);
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(
+        r#"{"power_mode":"Nom","hysteresis":"Off","output_stage":"DigitalPwm","pwm_frequency":"PwmF2","slow_filter":"X16","fast_filter_threshold":"SlowFilterOnly","watchdog_state":"Off"}"#,
+        json
+    );
+    assert_eq!(config, serde_json::from_str(&json).unwrap());
+
+    // Blank fields are specific to the device they were read from.
+    let read = Configuration::try_from(0b1100_0000_0000_0000).unwrap();
+    let back: Configuration = serde_json::from_str(&serde_json::to_string(&read).unwrap()).unwrap();
+    assert_eq!(None, back.blank_fields());
+}
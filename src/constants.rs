@@ -1,8 +1,17 @@
+use core::num::ParseIntError;
 use core::time::Duration;
 
 /// Default i2c address of AS5600.
 pub const DEFAULT_I2C_ADDRESS: u8 = 0x36;
 
+/// Parse an I2C address, decimal or hexadecimal with `0x` prefix.
+pub fn parse_address(address: &str) -> Result<u8, ParseIntError> {
+    match address.strip_prefix("0x") {
+        Some(hex) => u8::from_str_radix(hex, 16),
+        None => address.parse(),
+    }
+}
+
 /// Watchdog timeout duration (before it changes power modes).
 pub const WATCHDOG_TIMEOUT: Duration = Duration::from_secs(60);
 /// Time to power up AS5600.
@@ -11,6 +20,13 @@ pub const POWER_UP_TIME: Duration = Duration::from_millis(10);
 /// Sampling rate in normal power mode.
 pub const SAMPLE_RATE: Duration = Duration::from_micros(150);
 
+/// Polling time in low power mode 1.
+pub const POLLING_TIME_LPM1: Duration = Duration::from_millis(5);
+/// Polling time in low power mode 2.
+pub const POLLING_TIME_LPM2: Duration = Duration::from_millis(20);
+/// Polling time in low power mode 3.
+pub const POLLING_TIME_LPM3: Duration = Duration::from_millis(100);
+
 /// Settling time 1.
 pub const SETTLING_TIME_1: Duration = Duration::from_micros(2200);
 /// Settling time 2.
@@ -19,3 +35,12 @@ pub const SETTLING_TIME_2: Duration = Duration::from_micros(1100);
 pub const SETTLING_TIME_3: Duration = Duration::from_micros(550);
 /// Settling time 4.
 pub const SETTLING_TIME_4: Duration = Duration::from_micros(286);
+
+/// RMS output noise in degrees with settling time 1.
+pub const OUTPUT_NOISE_1: f32 = 0.015;
+/// RMS output noise in degrees with settling time 2.
+pub const OUTPUT_NOISE_2: f32 = 0.021;
+/// RMS output noise in degrees with settling time 3.
+pub const OUTPUT_NOISE_3: f32 = 0.030;
+/// RMS output noise in degrees with settling time 4.
+pub const OUTPUT_NOISE_4: f32 = 0.043;
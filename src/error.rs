@@ -1,9 +1,8 @@
-use crate::{configuration, status};
+use crate::{burn, configuration, multiturn, profile, register::Register, status};
 
 /// All possible errors in this crate.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-
 pub enum Error<E> {
     /// `I2C` communication error.
     Communication(E),
@@ -14,6 +13,22 @@ pub enum Error<E> {
     /// Configuration parsing/conversion error.
     Configuration(configuration::error::Error),
 
+    /// Burst read of more than 2 bytes starting at a register which holds the address pointer.
+    /// See [`Register::holds_pointer`].
+    InvalidBurstRead(Register),
+
+    /// Burning the OTP memory is denied by the burn policy.
+    BurnDenied,
+
+    /// Guided burn validation or verification error.
+    Burn(burn::Error),
+
+    /// Profile validation or verification error.
+    Profile(profile::Error),
+
+    /// Multi-turn tracking error.
+    MultiTurn(multiturn::Error),
+
     /// Persistence error: configuration can only be persisted 3 times.
     MaximumPositionPersistsReached,
 
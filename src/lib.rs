@@ -6,24 +6,53 @@
 
 #![doc = include_str!("../README.md")]
 
-pub use as5600::*;
+#[cfg(any(test, feature = "sim"))]
+extern crate alloc;
+
+pub use as5600::As5600;
 
 //#[cfg(feature = "async")]
 
+/// Angle values in sensor counts.
+pub mod angle;
+/// Guided and verified burning of the OTP memory.
+pub mod burn;
 /// Configuration of As5600.
 pub mod configuration;
 /// Constants.
 pub mod constants;
 /// Errors.
 pub mod error;
+/// Magnet placement assessment.
+pub mod magnet;
+/// Multi-turn position tracking.
+pub mod multiturn;
+/// Sensor provisioning profiles.
+pub mod profile;
 /// Registers.
-pub(crate) mod register;
+pub mod register;
+/// Simulated AS5600 device for testing.
+///
+/// Uses `alloc` for [`sim::Motion::Trace`], so `no_std` targets need a global allocator.
+#[cfg(any(test, feature = "sim"))]
+pub mod sim;
+/// Snapshots of register contents.
+pub mod snapshot;
 /// Magnet detection status.
 pub mod status;
+#[cfg(all(test, feature = "async"))]
+mod test_async;
+#[cfg(all(test, feature = "async"))]
+mod test_parity;
 #[cfg(test)]
 mod test_reading;
 #[cfg(test)]
 mod test_writing;
+/// Recording and replay of sensor traces.
+#[cfg(any(test, feature = "trace"))]
+pub mod trace;
+/// Angular velocity and acceleration estimation.
+pub mod velocity;
 
 mod as5600;
 
@@ -0,0 +1,227 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::status::StatusFlags;
+
+/// Supply voltage mode of the AS5600, which determines the range of the `AGC` register.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SupplyVoltage {
+    /// 3.3V operation (`VDD5V` connected to `VDD3V3`): AGC range is 0..=128.
+    V3_3,
+    /// 5V operation: AGC range is 0..=255.
+    V5,
+}
+
+impl SupplyVoltage {
+    /// Get the maximum `AGC` value in this supply voltage mode.
+    pub const fn agc_range(&self) -> u8 {
+        match self {
+            Self::V3_3 => 128,
+            Self::V5 => 255,
+        }
+    }
+}
+
+impl FromStr for SupplyVoltage {
+    type Err = ParseSupplyVoltageError;
+
+    /// Parse `3v3` or `5v`.
+    fn from_str(supply: &str) -> Result<Self, Self::Err> {
+        match supply {
+            "3v3" => Ok(Self::V3_3),
+            "5v" => Ok(Self::V5),
+            _ => Err(ParseSupplyVoltageError),
+        }
+    }
+}
+
+/// Error parsing a [`SupplyVoltage`] other than `3v3` or `5v`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ParseSupplyVoltageError;
+
+impl fmt::Display for ParseSupplyVoltageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "supply voltage must be `3v3` or `5v`")
+    }
+}
+
+impl core::error::Error for ParseSupplyVoltageError {}
+
+/// Classification of the magnet placement (air gap).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Placement {
+    /// Field too weak: magnet missing, too far away or too weak, or magnitude too low.
+    TooFar,
+    /// Field within the comfortable AGC range.
+    Good,
+    /// Field too strong: magnet too close or too strong.
+    TooClose,
+}
+
+/// Magnet placement report combining `STATUS`, `AGC` and `MAGNITUDE`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MagnetQuality {
+    /// Magnet detection flags.
+    pub status: StatusFlags,
+    /// Raw automatic gain control value.
+    pub agc: u8,
+    /// Raw magnitude of the magnetic field.
+    pub magnitude: u16,
+    /// Supply voltage mode used to normalise `agc`.
+    pub supply: SupplyVoltage,
+}
+
+impl MagnetQuality {
+    /// Normalised AGC values below this are considered too close.
+    pub const AGC_LOW_LIMIT: f32 = 0.1;
+    /// Normalised AGC values above this are considered too far.
+    pub const AGC_HIGH_LIMIT: f32 = 0.9;
+    /// Magnitudes below this are considered too far, as they leave little signal for the
+    /// angle measurement even within the AGC range.
+    pub const MAGNITUDE_LOW_LIMIT: u16 = 500;
+
+    /// Create a report from register values.
+    pub const fn new(status: StatusFlags, agc: u8, magnitude: u16, supply: SupplyVoltage) -> Self {
+        Self {
+            status,
+            agc,
+            magnitude,
+            supply,
+        }
+    }
+
+    /// AGC value normalised to `0.0..=1.0` for the supply voltage range.
+    ///
+    /// A low gain means a strong field, a high gain a weak field.
+    /// The middle of the range leaves the most headroom for temperature and air gap variations.
+    pub fn normalized_agc(&self) -> f32 {
+        let normalized = f32::from(self.agc) / f32::from(self.supply.agc_range());
+        normalized.min(1.0)
+    }
+
+    /// Classify the magnet placement.
+    pub fn placement(&self) -> Placement {
+        let agc = self.normalized_agc();
+        if !self.status.magnet_detected()
+            || self.status.too_weak()
+            || agc > Self::AGC_HIGH_LIMIT
+            || self.magnitude_too_low()
+        {
+            Placement::TooFar
+        } else if self.status.too_strong() || agc < Self::AGC_LOW_LIMIT {
+            Placement::TooClose
+        } else {
+            Placement::Good
+        }
+    }
+
+    /// Placement score from 0 (unusable) to 100 (AGC centered in its range).
+    ///
+    /// A magnitude below [`Self::MAGNITUDE_LOW_LIMIT`] lowers the score proportionally.
+    pub fn score(&self) -> u8 {
+        if !self.status.magnet_detected() {
+            return 0;
+        }
+        let distance = (self.normalized_agc() - 0.5).abs() * 2.0;
+        let mut score = (1.0 - distance) * 100.0;
+        if self.magnitude_too_low() {
+            score *= f32::from(self.magnitude) / f32::from(Self::MAGNITUDE_LOW_LIMIT);
+        }
+        // Round to nearest; the value is never negative.
+        let score = (score + 0.5) as u8;
+        if self.status.too_weak() || self.status.too_strong() {
+            score.min(25)
+        } else {
+            score
+        }
+    }
+
+    /// Advice for the operator.
+    pub fn advice(&self) -> &'static str {
+        if !self.status.magnet_detected() {
+            return "No magnet detected: check that the magnet is mounted above the sensor.";
+        }
+        match self.placement() {
+            Placement::TooFar if self.magnitude_too_low() => {
+                "Magnitude too low: reduce the air gap or use a stronger magnet."
+            }
+            Placement::TooFar => "Field too weak: reduce the air gap or use a stronger magnet.",
+            Placement::TooClose => "Field too strong: increase the air gap or use a weaker magnet.",
+            Placement::Good => "Magnet placement is good.",
+        }
+    }
+
+    /// Whether the magnitude is below [`Self::MAGNITUDE_LOW_LIMIT`].
+    fn magnitude_too_low(&self) -> bool {
+        self.magnitude < Self::MAGNITUDE_LOW_LIMIT
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::magnet::{MagnetQuality, ParseSupplyVoltageError, Placement, SupplyVoltage};
+    use crate::status::StatusFlags;
+
+    #[test]
+    fn classifies_placement() {
+        let quality =
+            MagnetQuality::new(StatusFlags::MAGNET_DETECTED, 128, 1500, SupplyVoltage::V5);
+        assert_eq!(Placement::Good, quality.placement());
+        assert_eq!(100, quality.score());
+
+        let quality = MagnetQuality::new(
+            StatusFlags::MAGNET_DETECTED | StatusFlags::MAGNET_HIGH,
+            5,
+            4000,
+            SupplyVoltage::V5,
+        );
+        assert_eq!(Placement::TooClose, quality.placement());
+        assert!(quality.score() <= 25);
+
+        let quality = MagnetQuality::new(StatusFlags::MAGNET_LOW, 255, 100, SupplyVoltage::V5);
+        assert_eq!(Placement::TooFar, quality.placement());
+        assert_eq!(0, quality.score());
+    }
+
+    #[test]
+    fn parses_supply_voltage() {
+        assert_eq!(Ok(SupplyVoltage::V3_3), "3v3".parse());
+        assert_eq!(Ok(SupplyVoltage::V5), "5v".parse());
+        assert_eq!(Err(ParseSupplyVoltageError), "3.3".parse::<SupplyVoltage>());
+    }
+
+    #[test]
+    fn normalizes_agc_for_supply_voltage() {
+        let v3 = MagnetQuality::new(StatusFlags::MAGNET_DETECTED, 64, 1500, SupplyVoltage::V3_3);
+        let v5 = MagnetQuality::new(StatusFlags::MAGNET_DETECTED, 64, 1500, SupplyVoltage::V5);
+        assert_eq!(0.5, v3.normalized_agc());
+        assert_eq!(Placement::Good, v3.placement());
+        assert!(v5.score() < v3.score());
+
+        let saturated =
+            MagnetQuality::new(StatusFlags::MAGNET_DETECTED, 200, 1500, SupplyVoltage::V3_3);
+        assert_eq!(1.0, saturated.normalized_agc());
+        assert_eq!(Placement::TooFar, saturated.placement());
+    }
+
+    #[test]
+    fn low_magnitude_lowers_placement_and_score() {
+        let quality = MagnetQuality::new(StatusFlags::MAGNET_DETECTED, 128, 200, SupplyVoltage::V5);
+        assert_eq!(Placement::TooFar, quality.placement());
+        assert_eq!(40, quality.score());
+        assert!(quality.advice().starts_with("Magnitude too low"));
+
+        let quality = MagnetQuality::new(
+            StatusFlags::MAGNET_DETECTED,
+            128,
+            MagnetQuality::MAGNITUDE_LOW_LIMIT,
+            SupplyVoltage::V5,
+        );
+        assert_eq!(Placement::Good, quality.placement());
+        assert_eq!(100, quality.score());
+    }
+}
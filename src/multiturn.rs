@@ -0,0 +1,310 @@
+use embedded_hal::i2c::I2c as BlockingI2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+use crate::angle::Angle;
+#[cfg(feature = "async")]
+use crate::as5600::As5600Async;
+use crate::As5600;
+
+/// Multi-turn tracking error.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// Two consecutive samples were further apart than the configured maximum step (in counts).
+    /// Sampling was too slow to tell the direction of rotation.
+    StepTooLarge(i16),
+    /// The revolution counter would overflow `i32`.
+    RevolutionOverflow,
+}
+
+/// Unwrapped multi-turn position.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Position {
+    /// Number of full revolutions (rounded towards negative infinity).
+    pub revolutions: i32,
+    /// Position within the current revolution.
+    pub angle: Angle,
+}
+
+impl Position {
+    /// Total position in counts, 4096 counts per revolution.
+    pub const fn total_counts(&self) -> i64 {
+        self.revolutions as i64 * Angle::COUNTS_PER_TURN as i64 + self.angle.counts() as i64
+    }
+
+    /// Total position in (fractional) turns.
+    pub fn turns(&self) -> f64 {
+        self.total_counts() as f64 / f64::from(Angle::COUNTS_PER_TURN)
+    }
+
+    /// Total position in degrees.
+    pub fn to_degrees(&self) -> f64 {
+        self.turns() * 360.0
+    }
+
+    /// Total position in radians.
+    pub fn to_radians(&self) -> f64 {
+        self.turns() * core::f64::consts::TAU
+    }
+}
+
+/// Bus-independent multi-turn tracker.
+///
+/// Feed it consecutive angle samples; wrap-arounds are detected using the shortest-path rule.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Tracker {
+    position: Option<Position>,
+    max_step: u16,
+}
+
+impl Default for Tracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tracker {
+    /// Default maximum step between two samples: a quarter turn.
+    pub const DEFAULT_MAX_STEP: u16 = Angle::COUNTS_PER_TURN / 4;
+
+    /// Create a new tracker with the default maximum step.
+    pub const fn new() -> Self {
+        Self::with_max_step(Self::DEFAULT_MAX_STEP)
+    }
+
+    /// Create a new tracker which rejects steps larger than `max_step` counts.
+    /// Values above 2047 counts are clamped, since larger steps are ambiguous anyway.
+    pub const fn with_max_step(max_step: u16) -> Self {
+        let max_step = if max_step > 2047 { 2047 } else { max_step };
+        Self {
+            position: None,
+            max_step,
+        }
+    }
+
+    /// Get the maximum step between two samples in counts.
+    pub const fn max_step(&self) -> u16 {
+        self.max_step
+    }
+
+    /// Get the current position, if at least one sample was accepted.
+    pub const fn position(&self) -> Option<Position> {
+        self.position
+    }
+
+    /// Forget the current position. The next sample starts at revolution zero.
+    pub fn reset(&mut self) {
+        self.position = None;
+    }
+
+    /// Accept `angle` as the current position without counting a revolution.
+    /// Use this to recover after [`Error::StepTooLarge`].
+    pub fn resync(&mut self, angle: Angle) {
+        let revolutions = self.position.map_or(0, |p| p.revolutions);
+        self.position = Some(Position { revolutions, angle });
+    }
+
+    /// Continue from a previously saved position, e.g. after a restart.
+    pub fn restore(&mut self, position: Position) {
+        self.position = Some(position);
+    }
+
+    /// Feed a new sample. The first sample defines revolution zero.
+    ///
+    /// On error, the sample is discarded and the previous position is kept.
+    /// Counting past `i32::MAX` or `i32::MIN` revolutions fails with
+    /// [`Error::RevolutionOverflow`] rather than wrapping around.
+    pub fn update(&mut self, angle: Angle) -> Result<Position, Error> {
+        let Some(previous) = self.position else {
+            let position = Position {
+                revolutions: 0,
+                angle,
+            };
+            self.position = Some(position);
+            return Ok(position);
+        };
+        let step = angle.shortest_difference(previous.angle);
+        if step.unsigned_abs() > self.max_step {
+            return Err(Error::StepTooLarge(step));
+        }
+        let unwrapped = i32::from(previous.angle.counts()) + i32::from(step);
+        let carry = if unwrapped < 0 {
+            -1
+        } else if unwrapped >= i32::from(Angle::COUNTS_PER_TURN) {
+            1
+        } else {
+            0
+        };
+        let revolutions = previous
+            .revolutions
+            .checked_add(carry)
+            .ok_or(Error::RevolutionOverflow)?;
+        let position = Position { revolutions, angle };
+        self.position = Some(position);
+        Ok(position)
+    }
+}
+
+/// Multi-turn tracker wrapping a driver instance.
+///
+/// Works with the blocking [`As5600`] and, with the `async` feature, `asynch::As5600`.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MultiTurn<D> {
+    driver: D,
+    tracker: Tracker,
+}
+
+impl<D> MultiTurn<D> {
+    /// Create a new multi-turn tracker with the default maximum step.
+    pub fn new(driver: D) -> Self {
+        Self::with_tracker(driver, Tracker::new())
+    }
+
+    /// Create a new multi-turn tracker using the given tracker state.
+    pub fn with_tracker(driver: D, tracker: Tracker) -> Self {
+        Self { driver, tracker }
+    }
+
+    /// Get the tracker state.
+    pub fn tracker(&self) -> &Tracker {
+        &self.tracker
+    }
+
+    /// Get the tracker state mutably, e.g. to reset or resync it.
+    pub fn tracker_mut(&mut self) -> &mut Tracker {
+        &mut self.tracker
+    }
+
+    /// Get the wrapped driver, e.g. to read other registers in between.
+    pub fn driver(&mut self) -> &mut D {
+        &mut self.driver
+    }
+
+    /// Get the current position, if at least one sample was taken.
+    pub fn position(&self) -> Option<Position> {
+        self.tracker.position()
+    }
+
+    /// Release the driver, consuming the tracker.
+    pub fn release(self) -> D {
+        self.driver
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(As5600(sync), AsyncI2c(sync = "BlockingI2c", async)),
+    keep_self,
+    sync(),
+    async(feature = "async")
+)]
+impl<I, E> MultiTurn<As5600<I>>
+where
+    I: AsyncI2c<Error = E>,
+{
+    /// Sample `RAW ANGLE` and update the multi-turn position.
+    pub async fn update(&mut self) -> Result<Position, crate::error::Error<E>> {
+        let angle = self.driver.raw_angle().await?;
+        self.tracker
+            .update(angle)
+            .map_err(crate::error::Error::MultiTurn)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+
+    use crate::angle::Angle;
+    use crate::error;
+    use crate::multiturn::{Error, MultiTurn, Position, Tracker};
+    use crate::As5600;
+
+    #[test]
+    fn counts_revolutions_in_both_directions() {
+        let mut tracker = Tracker::new();
+        let samples = [4000, 10, 1000, 2000, 3000, 4000, 20, 4000, 3000];
+        let expected = [0, 1, 1, 1, 1, 1, 2, 1, 1];
+        for (sample, revolutions) in samples.into_iter().zip(expected) {
+            let position = tracker.update(Angle::from_counts(sample)).unwrap();
+            assert_eq!(revolutions, position.revolutions);
+        }
+        assert_eq!(4096 + 3000, tracker.position().unwrap().total_counts());
+    }
+
+    #[test]
+    fn negative_positions_round_down() {
+        let mut tracker = Tracker::new();
+        tracker.update(Angle::from_counts(10)).unwrap();
+        let position = tracker.update(Angle::from_counts(4095)).unwrap();
+        assert_eq!(
+            Position {
+                revolutions: -1,
+                angle: Angle::from_counts(4095)
+            },
+            position
+        );
+        assert_eq!(-1, position.total_counts());
+        assert_eq!(-1.0 / 4096.0, position.turns());
+    }
+
+    #[test]
+    fn rejects_large_steps() {
+        let mut tracker = Tracker::with_max_step(100);
+        tracker.update(Angle::from_counts(0)).unwrap();
+        assert_eq!(
+            Err(Error::StepTooLarge(-200)),
+            tracker.update(Angle::from_counts(3896))
+        );
+        assert_eq!(Angle::ZERO, tracker.position().unwrap().angle);
+        tracker.resync(Angle::from_counts(3896));
+        assert_eq!(0, tracker.position().unwrap().revolutions);
+        assert_eq!(3896, tracker.position().unwrap().total_counts());
+    }
+
+    #[test]
+    fn rejects_revolution_overflow() {
+        let mut tracker = Tracker::new();
+        tracker.restore(Position {
+            revolutions: i32::MAX,
+            angle: Angle::from_counts(4000),
+        });
+        assert_eq!(
+            Err(Error::RevolutionOverflow),
+            tracker.update(Angle::from_counts(10))
+        );
+        assert_eq!(i32::MAX, tracker.position().unwrap().revolutions);
+        tracker.restore(Position {
+            revolutions: i32::MIN,
+            angle: Angle::from_counts(10),
+        });
+        assert_eq!(
+            Err(Error::RevolutionOverflow),
+            tracker.update(Angle::from_counts(4000))
+        );
+        assert_eq!(
+            i32::MIN,
+            tracker.update(Angle::from_counts(100)).unwrap().revolutions
+        );
+    }
+
+    #[test]
+    fn tracks_driver_raw_angle() {
+        let i2c = Mock::new(&[
+            Transaction::write_read(0x36, vec![0x0c], vec![0x0F, 0xF0]),
+            Transaction::write_read(0x36, vec![0x0c], vec![0x00, 0x10]),
+            Transaction::write_read(0x36, vec![0x0c], vec![0x08, 0x00]),
+        ]);
+        let mut multiturn = MultiTurn::new(As5600::new(i2c));
+        assert_eq!(0, multiturn.update().unwrap().revolutions);
+        assert_eq!(1, multiturn.update().unwrap().revolutions);
+        assert_eq!(
+            error::Error::MultiTurn(Error::StepTooLarge(0x7F0)),
+            multiturn.update().unwrap_err()
+        );
+        multiturn.release().release().done();
+    }
+}
@@ -0,0 +1,376 @@
+use crate::angle::Angle;
+use crate::burn::{self, BurnReport, OtpSnapshot};
+use crate::configuration::Configuration;
+use crate::snapshot::Field;
+
+/// Profile validation/verification error.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// The profile format version is not supported (version given).
+    UnsupportedVersion(u16),
+    /// Neither maximum position nor maximum angle is given.
+    MissingRange,
+    /// Both maximum position and maximum angle are given.
+    AmbiguousRange,
+    /// The angular range is below [`burn::MINIMUM_RANGE`] (counts given).
+    RangeTooSmall(u16),
+    /// A register read back after applying the profile does not hold the profile value.
+    Mismatch {
+        /// Mismatching register.
+        field: Field,
+        /// Value from the profile.
+        expected: u16,
+        /// Value read back.
+        actual: u16,
+    },
+}
+
+/// Settings to provision a sensor with.
+///
+/// The angular range is given either by the maximum position or by the maximum angle.
+/// With the `serde` feature, a profile can be stored as e.g. TOML:
+///
+/// ```toml
+/// version = 1
+/// zero_position = 100
+/// maximum_angle = 1024
+/// burn = false
+///
+/// [config]
+/// power_mode = "Nom"
+/// hysteresis = "Lsb1"
+/// output_stage = "Analog"
+/// pwm_frequency = "PwmF1"
+/// slow_filter = "X16"
+/// fast_filter_threshold = "SlowFilterOnly"
+/// watchdog_state = "Off"
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Profile {
+    /// Format version, see [`Profile::VERSION`].
+    pub version: u16,
+    /// Zero position.
+    pub zero_position: Angle,
+    /// Maximum position.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub maximum_position: Option<Angle>,
+    /// Maximum angle.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub maximum_angle: Option<Angle>,
+    /// Configuration.
+    pub config: Configuration,
+    /// Whether to burn the settings to the OTP memory after applying them.
+    pub burn: bool,
+}
+
+/// Result of applying a [`Profile`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ProfileReport {
+    /// Register contents read back after applying the profile.
+    pub readback: OtpSnapshot,
+    /// Burn of maximum angle and configuration, if the profile requests burning.
+    pub settings_burn: Option<BurnReport>,
+    /// Burn of zero and maximum position, if the profile requests burning.
+    pub angle_burn: Option<BurnReport>,
+}
+
+impl Profile {
+    /// Current format version.
+    pub const VERSION: u16 = 1;
+
+    /// Profile with the range given by zero and maximum position.
+    pub const fn with_positions(
+        zero_position: Angle,
+        maximum_position: Angle,
+        config: Configuration,
+    ) -> Self {
+        Self {
+            version: Self::VERSION,
+            zero_position,
+            maximum_position: Some(maximum_position),
+            maximum_angle: None,
+            config,
+            burn: false,
+        }
+    }
+
+    /// Profile with the range given by zero position and maximum angle.
+    pub const fn with_maximum_angle(
+        zero_position: Angle,
+        maximum_angle: Angle,
+        config: Configuration,
+    ) -> Self {
+        Self {
+            version: Self::VERSION,
+            zero_position,
+            maximum_position: None,
+            maximum_angle: Some(maximum_angle),
+            config,
+            burn: false,
+        }
+    }
+
+    /// Check the version and the datasheet constraints.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.version != Self::VERSION {
+            return Err(Error::UnsupportedVersion(self.version));
+        }
+        let (maximum_position, maximum_angle) = match (self.maximum_position, self.maximum_angle) {
+            (None, None) => return Err(Error::MissingRange),
+            (Some(_), Some(_)) => return Err(Error::AmbiguousRange),
+            (Some(maximum_position), None) => (maximum_position, Angle::ZERO),
+            (None, Some(maximum_angle)) => (Angle::ZERO, maximum_angle),
+        };
+        burn::check_range(self.zero_position, maximum_position, maximum_angle)
+            .map_err(Error::RangeTooSmall)?;
+        Ok(())
+    }
+
+    /// Compare the registers read back with the profile.
+    ///
+    /// The range register the profile leaves out must read back as zero.
+    pub(crate) fn verify(&self, readback: &OtpSnapshot) -> Result<(), Error> {
+        let config_bits = |config: Configuration| u16::from(config) & !Configuration::BLANK_FIELDS;
+        let mut fields = [
+            (
+                Field::ZeroPosition,
+                self.zero_position.counts(),
+                readback.zero_position.counts(),
+            ),
+            (
+                Field::MaximumPosition,
+                self.maximum_position.unwrap_or(Angle::ZERO).counts(),
+                readback.maximum_position.counts(),
+            ),
+            (
+                Field::MaximumAngle,
+                self.maximum_angle.unwrap_or(Angle::ZERO).counts(),
+                readback.maximum_angle.counts(),
+            ),
+            (
+                Field::Config,
+                config_bits(self.config),
+                config_bits(readback.config),
+            ),
+        ]
+        .into_iter();
+        match fields.find(|(_, expected, actual)| expected != actual) {
+            Some((field, expected, actual)) => Err(Error::Mismatch {
+                field,
+                expected,
+                actual,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+
+    use crate::angle::Angle;
+    use crate::burn::{BurnKind, BurnPolicy};
+    use crate::configuration::{Configuration, Hysteresis};
+    use crate::error;
+    use crate::profile::{Error, Profile};
+    use crate::sim::SimulatedAs5600;
+    use crate::snapshot::Field;
+    use crate::As5600;
+
+    #[test]
+    fn validates_datasheet_constraints() {
+        let config = Configuration::default();
+        let profile =
+            Profile::with_positions(Angle::from_counts(4000), Angle::from_counts(100), config);
+        assert_eq!(Err(Error::RangeTooSmall(196)), profile.validate());
+        // A maximum position of zero is unused, as in the burn pre-flight checks.
+        let profile = Profile::with_positions(Angle::from_counts(4000), Angle::ZERO, config);
+        assert_eq!(Ok(()), profile.validate());
+        let profile = Profile::with_maximum_angle(Angle::ZERO, Angle::from_counts(100), config);
+        assert_eq!(Err(Error::RangeTooSmall(100)), profile.validate());
+        let profile = Profile::with_maximum_angle(Angle::ZERO, Angle::ZERO, config);
+        assert_eq!(Ok(()), profile.validate());
+        let profile = Profile {
+            maximum_position: Some(Angle::MAX),
+            ..profile
+        };
+        assert_eq!(Err(Error::AmbiguousRange), profile.validate());
+        let profile = Profile {
+            maximum_position: None,
+            maximum_angle: None,
+            ..profile
+        };
+        assert_eq!(Err(Error::MissingRange), profile.validate());
+        let profile = Profile {
+            version: 2,
+            ..profile
+        };
+        assert_eq!(Err(Error::UnsupportedVersion(2)), profile.validate());
+    }
+
+    #[test]
+    fn applies_and_verifies() {
+        let config = Configuration::low_noise();
+        let profile =
+            Profile::with_positions(Angle::from_counts(100), Angle::from_counts(1100), config);
+        let mut as5600 = As5600::new(SimulatedAs5600::new());
+        let report = as5600.apply_profile(&profile, &mut NoopDelay).unwrap();
+        assert_eq!(Angle::from_counts(1100), report.readback.maximum_position);
+        assert_eq!(Hysteresis::Lsb1, report.readback.config.hysteresis);
+        assert_eq!(None, report.angle_burn);
+        assert_eq!(0, as5600.release().zmco());
+    }
+
+    #[test]
+    fn clears_unused_range_register() {
+        let mut as5600 = As5600::new(SimulatedAs5600::new());
+        as5600
+            .set_maximum_position(Angle::from_counts(3000))
+            .unwrap();
+        let profile = Profile::with_maximum_angle(
+            Angle::from_counts(100),
+            Angle::from_counts(1024),
+            Configuration::default(),
+        );
+        let report = as5600.apply_profile(&profile, &mut NoopDelay).unwrap();
+        assert_eq!(Angle::ZERO, report.readback.maximum_position);
+        assert_eq!(Angle::from_counts(1024), report.readback.maximum_angle);
+
+        let profile = Profile::with_positions(
+            Angle::from_counts(100),
+            Angle::from_counts(1100),
+            profile.config,
+        );
+        let report = as5600.apply_profile(&profile, &mut NoopDelay).unwrap();
+        assert_eq!(Angle::ZERO, report.readback.maximum_angle);
+        assert_eq!(Angle::from_counts(1100), report.readback.maximum_position);
+    }
+
+    #[test]
+    fn detects_mismatch() {
+        let profile = Profile::with_maximum_angle(
+            Angle::from_counts(100),
+            Angle::from_counts(1000),
+            Configuration::default(),
+        );
+        let readback = crate::burn::OtpSnapshot {
+            zero_position: Angle::from_counts(100),
+            maximum_position: Angle::ZERO,
+            maximum_angle: Angle::from_counts(999),
+            config: Configuration::default(),
+        };
+        assert_eq!(
+            Err(Error::Mismatch {
+                field: Field::MaximumAngle,
+                expected: 1000,
+                actual: 999
+            }),
+            profile.verify(&readback)
+        );
+        let readback = crate::burn::OtpSnapshot {
+            maximum_position: Angle::from_counts(3000),
+            maximum_angle: Angle::from_counts(1000),
+            ..readback
+        };
+        assert_eq!(
+            Err(Error::Mismatch {
+                field: Field::MaximumPosition,
+                expected: 0,
+                actual: 3000
+            }),
+            profile.verify(&readback)
+        );
+    }
+
+    #[test]
+    fn burns_settings_then_angle() {
+        let profile = Profile {
+            burn: true,
+            ..Profile::with_maximum_angle(
+                Angle::from_counts(100),
+                Angle::from_counts(1024),
+                Configuration::low_power(),
+            )
+        };
+        let mut as5600 = As5600::new(SimulatedAs5600::new());
+        let report = as5600.apply_profile(&profile, &mut NoopDelay).unwrap();
+        let settings_burn = report.settings_burn.unwrap();
+        assert_eq!(BurnKind::Setting, settings_burn.kind);
+        assert_eq!(1, report.angle_burn.unwrap().zmco_after);
+        let otp = as5600.release().otp();
+        assert_eq!(Angle::from_counts(1024), otp.maximum_angle);
+        assert_eq!(Angle::from_counts(100), otp.zero_position);
+        assert_eq!(u16::from(Configuration::low_power()), otp.config);
+    }
+
+    #[test]
+    fn dry_run_burns_nothing() {
+        let profile = Profile {
+            burn: true,
+            ..Profile::with_positions(
+                Angle::from_counts(100),
+                Angle::from_counts(1100),
+                Configuration::default(),
+            )
+        };
+        let mut as5600 = As5600::new(SimulatedAs5600::new());
+        as5600.set_burn_policy(BurnPolicy::DryRun);
+        let report = as5600.apply_profile(&profile, &mut NoopDelay).unwrap();
+        assert!(!report.settings_burn.unwrap().outcome.burned());
+        assert!(!report.angle_burn.unwrap().outcome.burned());
+        assert_eq!(Angle::ZERO, as5600.release().otp().zero_position);
+    }
+
+    #[test]
+    fn rejects_invalid_profile_before_writing() {
+        let profile = Profile {
+            version: 0,
+            ..Profile::with_maximum_angle(Angle::ZERO, Angle::ZERO, Configuration::default())
+        };
+        let mut as5600 = As5600::new(SimulatedAs5600::new());
+        assert_eq!(
+            Err(error::Error::Profile(Error::UnsupportedVersion(0))),
+            as5600.apply_profile(&profile, &mut NoopDelay)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn loads_from_toml_and_json() {
+        let toml = r#"
+            version = 1
+            zero_position = 100
+            maximum_angle = 1024
+            burn = false
+
+            [config]
+            power_mode = "Nom"
+            hysteresis = "Lsb1"
+            output_stage = "Analog"
+            pwm_frequency = "PwmF1"
+            slow_filter = "X16"
+            fast_filter_threshold = "SlowFilterOnly"
+            watchdog_state = "Off"
+        "#;
+        let profile: Profile = toml::from_str(toml).unwrap();
+        assert_eq!(
+            Profile::with_maximum_angle(
+                Angle::from_counts(100),
+                Angle::from_counts(1024),
+                Configuration::low_noise()
+            ),
+            profile
+        );
+        let json = serde_json::to_string(&profile).unwrap();
+        assert_eq!(profile, serde_json::from_str(&json).unwrap());
+
+        // Counts above 4095 are rejected rather than masked.
+        let toml = toml.replace("zero_position = 100", "zero_position = 5000");
+        assert!(toml::from_str::<Profile>(&toml).is_err());
+    }
+}
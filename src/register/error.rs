@@ -1,6 +1,8 @@
+/// Register parsing error.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[non_exhaustive]
 pub enum Error {
+    /// Not a register address.
     Register(u8),
 }
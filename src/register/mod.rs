@@ -1,8 +1,12 @@
 #[cfg(test)]
 use proptest_derive::Arbitrary;
 
+/// Errors.
 pub mod error;
+#[cfg(any(test, feature = "sim", feature = "trace"))]
+pub(crate) mod pointer;
 
+/// Register addresses.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(test, derive(Arbitrary))]
@@ -34,9 +38,20 @@ pub enum Register {
     #[cfg(feature = "as5600l")]
     /// I2C update register (AS5600L only).
     I2CUPDT = 0x21,
+    /// This register accepts the burn and OTP reload commands.
     Burn = 0xFF,
 }
 
+impl Register {
+    /// Whether the address pointer stays on this register instead of auto-incrementing.
+    ///
+    /// After addressing `RAW_ANGLE`, `ANGLE` or `MAGNITUDE`, consecutive reads alternate
+    /// between the high and the low byte, see datasheet.
+    pub const fn holds_pointer(self) -> bool {
+        matches!(self, Self::RawAngle | Self::Angle | Self::Magnitude)
+    }
+}
+
 impl From<Register> for u8 {
     fn from(reg: Register) -> Self {
         reg as Self
@@ -0,0 +1,78 @@
+use super::Register;
+
+/// Model of the device's address pointer, shared by the simulated device and trace replay.
+///
+/// Writing a register address sets the pointer. Reads and writes advance it by one byte,
+/// except that after addressing `RAW ANGLE`, `ANGLE` or `MAGNITUDE` (see
+/// [`Register::holds_pointer`]) consecutive reads alternate between the high and the low byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct AddressPointer {
+    address: u8,
+    /// High byte of a non-incrementing register, if the pointer was explicitly set to it.
+    locked: Option<u8>,
+}
+
+impl AddressPointer {
+    /// Pointer after power-up, at register `ZMCO`.
+    pub(crate) const fn new() -> Self {
+        Self {
+            address: 0,
+            locked: None,
+        }
+    }
+
+    /// Register address the pointer is at.
+    pub(crate) const fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Set the pointer to the register address written first in a transaction.
+    pub(crate) fn set(&mut self, address: u8) {
+        self.address = address;
+        let locking = Register::try_from(address).is_ok_and(Register::holds_pointer);
+        self.locked = locking.then_some(address);
+    }
+
+    /// Advance after a byte was read.
+    pub(crate) fn advance_read(&mut self) {
+        self.address = match self.locked {
+            // Alternate between high and low byte instead of incrementing.
+            Some(high) if self.address != high => high,
+            _ => self.address.wrapping_add(1),
+        };
+    }
+
+    /// Advance after a byte was written, which always increments.
+    pub(crate) fn advance_write(&mut self) {
+        self.address = self.address.wrapping_add(1);
+        self.locked = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::register::pointer::AddressPointer;
+    use crate::register::Register;
+
+    #[test]
+    fn alternates_on_registers_which_hold_the_pointer() {
+        let mut pointer = AddressPointer::new();
+        pointer.set(Register::RawAngle.into());
+        let addresses: [u8; 4] = core::array::from_fn(|_| {
+            let address = pointer.address();
+            pointer.advance_read();
+            address
+        });
+        assert_eq!([0x0C, 0x0D, 0x0C, 0x0D], addresses);
+
+        pointer.set(Register::Zpos.into());
+        pointer.advance_read();
+        pointer.advance_read();
+        assert_eq!(0x03, pointer.address());
+
+        pointer.set(Register::Magnitude.into());
+        pointer.advance_write();
+        pointer.advance_read();
+        assert_eq!(0x1D, pointer.address());
+    }
+}
@@ -0,0 +1,558 @@
+use core::time::Duration;
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, NoAcknowledgeSource, Operation};
+
+use crate::angle::Angle;
+use crate::burn::{BurnKind, LOAD_OTP_COMMANDS};
+use crate::configuration::{Configuration, FastFilterThreshold, SlowFilterMode};
+use crate::constants::{DEFAULT_I2C_ADDRESS, SAMPLE_RATE, SETTLING_TIME_4};
+use crate::magnet::SupplyVoltage;
+use crate::register::pointer::AddressPointer;
+use crate::status::StatusFlags;
+
+pub use self::motion::Motion;
+use self::motion::{Field, Noise};
+
+mod motion;
+#[cfg(test)]
+mod test;
+
+const BURN_ANGLE: u8 = BurnKind::Angle.command();
+const BURN_SETTING: u8 = BurnKind::Setting.command();
+
+/// Simulated bus error.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// No device answered at the given address.
+    NoAcknowledge(u8),
+}
+
+impl embedded_hal::i2c::Error for Error {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::NoAcknowledge(_) => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+        }
+    }
+}
+
+/// Permanently programmed (OTP) register contents.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Otp {
+    /// Burned zero position.
+    pub zero_position: Angle,
+    /// Burned maximum position.
+    pub maximum_position: Angle,
+    /// Burned maximum angle.
+    pub maximum_angle: Angle,
+    /// Burned configuration, including the blank bits.
+    pub config: u16,
+    /// Burned I2C address (AS5600L only).
+    #[cfg(feature = "as5600l")]
+    pub address: u8,
+}
+
+/// Stateful simulation of an AS5600 implementing the `I2c` traits.
+///
+/// Models the register map, the address pointer (including its special behaviour on
+/// `RAW ANGLE`, `ANGLE` and `MAGNITUDE`), the scaling of `ANGLE` by `ZPOS`, `MPOS` and `MANG`,
+/// the output hysteresis, the `ZMCO` burn counter and the `BURN` commands.
+/// The magnet is controlled by the test via setters like [`SimulatedAs5600::set_raw_angle`],
+/// or by a scripted [`Motion`] as simulated time advances.
+///
+/// Simulated time only advances by [`SimulatedAs5600::advance`], and optionally by a fixed
+/// tick on every bus transaction (see [`SimulatedAs5600::set_tick`]). The device samples the
+/// magnet at [`SAMPLE_RATE`] and filters `ANGLE` with the configured slow and fast filters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatedAs5600 {
+    address: u8,
+    pointer: AddressPointer,
+    zmco: u8,
+    settings_burned: bool,
+    zero_position: u16,
+    maximum_position: u16,
+    maximum_angle: u16,
+    config: u16,
+    otp: Otp,
+    load_otp_progress: usize,
+    raw_angle: Angle,
+    angle: Angle,
+    status: StatusFlags,
+    agc: u8,
+    magnitude: u16,
+    #[cfg(feature = "as5600l")]
+    address_register: u8,
+    time: Duration,
+    next_sample: Duration,
+    tick: Duration,
+    motion: Motion,
+    motion_start: Duration,
+    /// Noise-free magnet position in fractional counts.
+    magnet: f32,
+    /// Output of the slow/fast filter in fractional counts.
+    filtered: f32,
+    noise: Noise,
+    distance: Option<f32>,
+    supply: SupplyVoltage,
+}
+
+impl Default for SimulatedAs5600 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulatedAs5600 {
+    /// Create a fresh device at the default address with a well-placed magnet at angle zero.
+    pub fn new() -> Self {
+        Self::with_address(DEFAULT_I2C_ADDRESS)
+    }
+
+    /// Create a fresh device at the given address.
+    pub fn with_address(address: u8) -> Self {
+        Self {
+            address,
+            pointer: AddressPointer::new(),
+            zmco: 0,
+            settings_burned: false,
+            zero_position: 0,
+            maximum_position: 0,
+            maximum_angle: 0,
+            config: 0,
+            otp: Otp {
+                #[cfg(feature = "as5600l")]
+                address,
+                ..Otp::default()
+            },
+            load_otp_progress: 0,
+            raw_angle: Angle::ZERO,
+            angle: Angle::ZERO,
+            status: StatusFlags::MAGNET_DETECTED,
+            agc: 128,
+            magnitude: 2048,
+            #[cfg(feature = "as5600l")]
+            address_register: address,
+            time: Duration::ZERO,
+            next_sample: Duration::ZERO,
+            tick: Duration::ZERO,
+            motion: Motion::Stationary,
+            motion_start: Duration::ZERO,
+            magnet: 0.0,
+            filtered: 0.0,
+            noise: Noise::new(0),
+            distance: None,
+            supply: SupplyVoltage::V5,
+        }
+    }
+
+    /// Program factory settings into the blank bits of `CONF`.
+    pub fn with_factory_config_bits(mut self, bits: u16) -> Self {
+        let bits = bits & Configuration::BLANK_FIELDS;
+        self.otp.config = (self.otp.config & !Configuration::BLANK_FIELDS) | bits;
+        self.config = (self.config & !Configuration::BLANK_FIELDS) | bits;
+        self
+    }
+
+    /// Get the I2C address the device currently answers to.
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Get the current address pointer.
+    pub fn pointer(&self) -> u8 {
+        self.pointer.address()
+    }
+
+    /// Get the number of times `ZPOS` and `MPOS` were burned.
+    pub fn zmco(&self) -> u8 {
+        self.zmco
+    }
+
+    /// Get the zero position shadow register.
+    pub fn zero_position(&self) -> Angle {
+        Angle::from_counts(self.zero_position)
+    }
+
+    /// Get the maximum position shadow register.
+    pub fn maximum_position(&self) -> Angle {
+        Angle::from_counts(self.maximum_position)
+    }
+
+    /// Get the maximum angle shadow register.
+    pub fn maximum_angle(&self) -> Angle {
+        Angle::from_counts(self.maximum_angle)
+    }
+
+    /// Get the configuration shadow register, including the blank bits.
+    pub fn config(&self) -> u16 {
+        self.config
+    }
+
+    /// Get the permanently programmed register contents.
+    pub fn otp(&self) -> Otp {
+        self.otp
+    }
+
+    /// Whether the blank bits of `CONF` still hold the factory settings.
+    pub fn factory_config_bits_intact(&self) -> bool {
+        self.config & Configuration::BLANK_FIELDS == self.otp.config & Configuration::BLANK_FIELDS
+    }
+
+    /// Simulate a power cycle: shadow registers are loaded from OTP.
+    pub fn power_cycle(&mut self) {
+        self.load_otp();
+        self.pointer = AddressPointer::new();
+        #[cfg(feature = "as5600l")]
+        {
+            self.address = self.otp.address;
+            self.address_register = self.otp.address;
+        }
+    }
+
+    /// Get the physical magnet angle.
+    pub fn raw_angle(&self) -> Angle {
+        self.raw_angle
+    }
+
+    /// Move the magnet to the given angle instantly, bypassing noise and filters.
+    pub fn set_raw_angle(&mut self, angle: Angle) {
+        self.raw_angle = angle;
+        self.magnet = f32::from(angle.counts());
+        self.filtered = self.magnet;
+        self.update_output();
+    }
+
+    /// Get the simulated time.
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    /// Advance simulated time automatically by `tick` on every bus transaction.
+    pub fn set_tick(&mut self, tick: Duration) {
+        self.tick = tick;
+    }
+
+    /// Start a scripted motion at the current simulated time.
+    pub fn set_motion(&mut self, motion: Motion) {
+        self.motion = motion;
+        self.motion_start = self.time;
+    }
+
+    /// Add gaussian noise with the given standard deviation in counts to every sample.
+    /// The noise sequence is deterministic for a given `seed`.
+    pub fn set_noise(&mut self, std_dev: f32, seed: u32) {
+        self.noise = Noise::new(seed);
+        self.noise.std_dev = std_dev;
+    }
+
+    /// Set the supply voltage mode, which determines the range of `AGC`.
+    pub fn set_supply(&mut self, supply: SupplyVoltage) {
+        self.supply = supply;
+        if let Some(distance) = self.distance {
+            self.set_distance(distance);
+        }
+    }
+
+    /// Place the magnet at the given air gap in mm, deriving `STATUS`, `AGC` and `MAGNITUDE`.
+    ///
+    /// The model centers the AGC at 1.5mm, reports the magnet too strong below roughly 0.2mm,
+    /// too weak beyond 3mm and not detected beyond 4mm.
+    pub fn set_distance(&mut self, distance: f32) {
+        self.distance = Some(distance);
+        let field = Field::at_distance(distance, self.supply.agc_range());
+        let mut status = StatusFlags::default();
+        for (flag, set) in [
+            (StatusFlags::MAGNET_DETECTED, field.detected),
+            (StatusFlags::MAGNET_LOW, field.too_weak),
+            (StatusFlags::MAGNET_HIGH, field.too_strong),
+        ] {
+            if set {
+                status = status | flag;
+            }
+        }
+        self.status = status;
+        self.agc = field.agc;
+        self.magnitude = field.magnitude;
+    }
+
+    /// Advance simulated time, sampling the magnet at the device sample rate.
+    pub fn advance(&mut self, duration: Duration) {
+        self.time += duration;
+        while self.next_sample <= self.time {
+            self.sample(self.next_sample);
+            self.next_sample += SAMPLE_RATE;
+        }
+    }
+
+    /// Take one sample of the magnet and run it through the filters.
+    fn sample(&mut self, time: Duration) {
+        if let Some(position) = self.motion.position(time - self.motion_start) {
+            self.magnet = position;
+        }
+        let measured = wrap(libm::roundf(self.magnet + self.noise.sample()));
+        self.raw_angle = Angle::from_counts(measured as u16);
+
+        let mut step = wrap(measured - self.filtered);
+        if step >= f32::from(Angle::COUNTS_PER_TURN / 2) {
+            step -= f32::from(Angle::COUNTS_PER_TURN);
+        }
+        let config = self.config;
+        let slow_filter =
+            SlowFilterMode::try_from(((config >> 8) & 0b11) as u8).unwrap_or(SlowFilterMode::X16);
+        let fast_filter = FastFilterThreshold::try_from(((config >> 10) & 0b111) as u8)
+            .unwrap_or(FastFilterThreshold::SlowFilterOnly);
+        let settling_time = match fast_filter_threshold(fast_filter) {
+            Some(threshold) if libm::fabsf(step) > threshold => SETTLING_TIME_4,
+            _ => slow_filter.settling_time(),
+        };
+        // First order low pass which settles to within 1% of a step in the settling time.
+        let time_constant = settling_time.as_secs_f32() / 4.6;
+        let alpha = 1.0 - libm::expf(-SAMPLE_RATE.as_secs_f32() / time_constant);
+        self.filtered = wrap(self.filtered + alpha * step);
+        self.update_output();
+    }
+
+    /// Get the scaled output angle as reported by register `ANGLE`.
+    pub fn angle(&self) -> Angle {
+        self.angle
+    }
+
+    /// Set the magnet detection flags.
+    pub fn set_status(&mut self, status: StatusFlags) {
+        self.status = status;
+    }
+
+    /// Set the automatic gain control value.
+    pub fn set_agc(&mut self, agc: u8) {
+        self.agc = agc;
+    }
+
+    /// Set the magnitude of the magnetic field (12 bits).
+    pub fn set_magnitude(&mut self, magnitude: u16) {
+        self.magnitude = magnitude & 0x0FFF;
+    }
+
+    /// Angular range in counts which is mapped to the full output range.
+    fn range(&self) -> u16 {
+        let start = Angle::from_counts(self.zero_position);
+        let stop = Angle::from_counts(self.maximum_position);
+        if self.maximum_position != 0 && stop != start {
+            (stop - start).counts()
+        } else if self.maximum_angle != 0 {
+            self.maximum_angle
+        } else {
+            Angle::COUNTS_PER_TURN
+        }
+    }
+
+    /// Scale the filtered angle into the output range, without hysteresis.
+    fn scaled_angle(&self) -> Angle {
+        let filtered = Angle::from_counts(libm::roundf(self.filtered) as u16);
+        let relative = (filtered - Angle::from_counts(self.zero_position)).counts();
+        let range = self.range();
+        if range == Angle::COUNTS_PER_TURN {
+            return Angle::from_counts(relative);
+        }
+        if relative > range {
+            // Outside of the range, the output saturates at the nearer end.
+            let beyond = relative - range;
+            let before = Angle::COUNTS_PER_TURN - relative;
+            return if beyond < before {
+                Angle::MAX
+            } else {
+                Angle::ZERO
+            };
+        }
+        let scaled = u32::from(relative) * u32::from(Angle::COUNTS_PER_TURN) / u32::from(range);
+        Angle::from_counts(scaled.min(u32::from(Angle::MAX.counts())) as u16)
+    }
+
+    /// Update register `ANGLE`, applying the configured hysteresis.
+    fn update_output(&mut self) {
+        let target = self.scaled_angle();
+        let hysteresis = ((self.config >> 2) & 0b11) as i16;
+        if target.shortest_difference(self.angle).abs() > hysteresis {
+            self.angle = target;
+        }
+    }
+
+    fn load_otp(&mut self) {
+        self.zero_position = self.otp.zero_position.counts();
+        self.maximum_position = self.otp.maximum_position.counts();
+        self.maximum_angle = self.otp.maximum_angle.counts();
+        self.config = self.otp.config;
+        self.update_output();
+    }
+
+    fn burn(&mut self, command: u8) {
+        match command {
+            // Requires a magnet and at most 3 previous burns, otherwise the device ignores it.
+            BURN_ANGLE if self.zmco < 3 && self.status.magnet_detected() => {
+                self.otp.zero_position = Angle::from_counts(self.zero_position);
+                self.otp.maximum_position = Angle::from_counts(self.maximum_position);
+                self.zmco += 1;
+            }
+            // Only possible once, and only before any angle burn.
+            BURN_SETTING if self.zmco == 0 && !self.settings_burned => {
+                self.otp.maximum_angle = Angle::from_counts(self.maximum_angle);
+                self.otp.config = self.config;
+                #[cfg(feature = "as5600l")]
+                {
+                    self.otp.address = self.address_register;
+                }
+                self.settings_burned = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn command(&mut self, command: u8) {
+        if command == LOAD_OTP_COMMANDS[self.load_otp_progress] {
+            self.load_otp_progress += 1;
+            if self.load_otp_progress == LOAD_OTP_COMMANDS.len() {
+                self.load_otp_progress = 0;
+                self.load_otp();
+            }
+            return;
+        }
+        self.load_otp_progress = usize::from(command == LOAD_OTP_COMMANDS[0]);
+        self.burn(command);
+    }
+
+    fn read_register(&self, address: u8) -> u8 {
+        let [zpos_h, zpos_l] = self.zero_position.to_be_bytes();
+        let [mpos_h, mpos_l] = self.maximum_position.to_be_bytes();
+        let [mang_h, mang_l] = self.maximum_angle.to_be_bytes();
+        let [conf_h, conf_l] = self.config.to_be_bytes();
+        let [raw_h, raw_l] = self.raw_angle.counts().to_be_bytes();
+        let [angle_h, angle_l] = self.angle.counts().to_be_bytes();
+        let [magnitude_h, magnitude_l] = self.magnitude.to_be_bytes();
+        match address {
+            0x00 => self.zmco,
+            0x01 => zpos_h,
+            0x02 => zpos_l,
+            0x03 => mpos_h,
+            0x04 => mpos_l,
+            0x05 => mang_h,
+            0x06 => mang_l,
+            0x07 => conf_h,
+            0x08 => conf_l,
+            0x0B => self.status.bits(),
+            0x0C => raw_h,
+            0x0D => raw_l,
+            0x0E => angle_h,
+            0x0F => angle_l,
+            0x1A => self.agc,
+            0x1B => magnitude_h,
+            0x1C => magnitude_l,
+            #[cfg(feature = "as5600l")]
+            0x20 => self.address_register << 1,
+            #[cfg(feature = "as5600l")]
+            0x21 => self.address << 1,
+            _ => 0,
+        }
+    }
+
+    fn write_register(&mut self, address: u8, value: u8) {
+        let high = |register: u16| (register & 0x00FF) | u16::from(value & 0x0F) << 8;
+        let low = |register: u16| (register & 0xFF00) | u16::from(value);
+        match address {
+            0x01 => self.zero_position = high(self.zero_position),
+            0x02 => self.zero_position = low(self.zero_position),
+            0x03 => self.maximum_position = high(self.maximum_position),
+            0x04 => self.maximum_position = low(self.maximum_position),
+            0x05 => self.maximum_angle = high(self.maximum_angle),
+            0x06 => self.maximum_angle = low(self.maximum_angle),
+            0x07 => self.config = (self.config & 0x00FF) | u16::from(value) << 8,
+            0x08 => self.config = low(self.config),
+            0xFF => self.command(value),
+            #[cfg(feature = "as5600l")]
+            0x20 => self.address_register = value >> 1,
+            #[cfg(feature = "as5600l")]
+            0x21 => self.address = value >> 1,
+            // Read-only and unused registers ignore writes.
+            _ => {}
+        }
+        self.update_output();
+    }
+
+    fn execute(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), Error> {
+        self.advance(self.tick);
+        if address != self.address {
+            return Err(Error::NoAcknowledge(address));
+        }
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    let Some((&pointer, data)) = bytes.split_first() else {
+                        continue;
+                    };
+                    self.pointer.set(pointer);
+                    for &byte in data {
+                        self.write_register(self.pointer.address(), byte);
+                        self.pointer.advance_write();
+                    }
+                }
+                Operation::Read(buffer) => {
+                    for byte in buffer.iter_mut() {
+                        *byte = self.read_register(self.pointer.address());
+                        self.pointer.advance_read();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Wrap fractional counts into `[0, 4096)`.
+fn wrap(counts: f32) -> f32 {
+    let turn = f32::from(Angle::COUNTS_PER_TURN);
+    let wrapped = counts - turn * libm::floorf(counts / turn);
+    // Rounding may produce exactly one full turn for tiny negative inputs.
+    if wrapped >= turn {
+        0.0
+    } else {
+        wrapped
+    }
+}
+
+/// Step size in counts above which the fast filter takes over.
+fn fast_filter_threshold(threshold: FastFilterThreshold) -> Option<f32> {
+    match threshold {
+        FastFilterThreshold::SlowFilterOnly => None,
+        FastFilterThreshold::Lsb6 => Some(6.0),
+        FastFilterThreshold::Lsb7 => Some(7.0),
+        FastFilterThreshold::Lsb9 => Some(9.0),
+        FastFilterThreshold::Lsb18 => Some(18.0),
+        FastFilterThreshold::Lsb21 => Some(21.0),
+        FastFilterThreshold::Lsb24 => Some(24.0),
+        FastFilterThreshold::Lsb10 => Some(10.0),
+    }
+}
+
+impl ErrorType for SimulatedAs5600 {
+    type Error = Error;
+}
+
+impl embedded_hal::i2c::I2c for SimulatedAs5600 {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.execute(address, operations)
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c for SimulatedAs5600 {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.execute(address, operations)
+    }
+}
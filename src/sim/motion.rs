@@ -0,0 +1,154 @@
+use alloc::vec::Vec;
+use core::f32::consts::TAU;
+use core::time::Duration;
+
+use crate::angle::Angle;
+
+/// Scripted magnet motion for [`super::SimulatedAs5600`].
+///
+/// Time is measured from the moment the motion is set.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Motion {
+    /// Magnet does not move.
+    Stationary,
+    /// Rotation at constant speed.
+    ConstantSpeed {
+        /// Angle at time zero.
+        start: Angle,
+        /// Speed in counts per second, negative for reverse rotation.
+        counts_per_second: f32,
+    },
+    /// Sinusoidal sweep around a center angle.
+    Sine {
+        /// Center of the sweep.
+        center: Angle,
+        /// Amplitude in counts.
+        amplitude: f32,
+        /// Frequency in Hz.
+        frequency: f32,
+    },
+    /// Jump from one angle to another.
+    Step {
+        /// Angle before the step.
+        from: Angle,
+        /// Angle after the step.
+        to: Angle,
+        /// Time of the step.
+        at: Duration,
+    },
+    /// Playback of equidistant recorded samples. The last sample is held at the end.
+    ///
+    /// The samples are allocated, see the [`sim`](crate::sim) module.
+    Trace {
+        /// Recorded angles.
+        samples: Vec<Angle>,
+        /// Time between two samples.
+        interval: Duration,
+    },
+}
+
+impl Motion {
+    /// Magnet angle in (fractional) counts at time `t`, or `None` if the magnet does not move.
+    pub fn position(&self, t: Duration) -> Option<f32> {
+        let seconds = t.as_secs_f32();
+        match self {
+            Self::Stationary => None,
+            Self::ConstantSpeed {
+                start,
+                counts_per_second,
+            } => Some(f32::from(start.counts()) + counts_per_second * seconds),
+            Self::Sine {
+                center,
+                amplitude,
+                frequency,
+            } => {
+                Some(f32::from(center.counts()) + amplitude * libm::sinf(TAU * frequency * seconds))
+            }
+            Self::Step { from, to, at } => {
+                Some(f32::from(if t < *at { from } else { to }.counts()))
+            }
+            Self::Trace { samples, interval } => {
+                let index = if interval.is_zero() {
+                    usize::MAX
+                } else {
+                    (t.as_nanos() / interval.as_nanos()) as usize
+                };
+                let sample = samples.get(index).or(samples.last())?;
+                Some(f32::from(sample.counts()))
+            }
+        }
+    }
+}
+
+/// Deterministic gaussian noise source (xorshift32 and Irwin-Hall approximation).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Noise {
+    state: u32,
+    /// Standard deviation in counts.
+    pub(crate) std_dev: f32,
+}
+
+impl Noise {
+    pub(crate) const fn new(seed: u32) -> Self {
+        Self {
+            // xorshift must not be seeded with zero.
+            state: if seed == 0 { 0x2545_F491 } else { seed },
+            std_dev: 0.0,
+        }
+    }
+
+    fn next_uniform(&mut self) -> f32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        (self.state >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    pub(crate) fn sample(&mut self) -> f32 {
+        if self.std_dev == 0.0 {
+            return 0.0;
+        }
+        // The sum of 12 uniform samples has variance 1.
+        let sum: f32 = (0..12).map(|_| self.next_uniform()).sum();
+        (sum - 6.0) * self.std_dev
+    }
+}
+
+/// Magnetic field model driving `STATUS`, `AGC` and `MAGNITUDE` from the air gap.
+///
+/// The field strength falls off with the cube of the air gap. The AGC partially compensates,
+/// its gain rising linearly with the air gap until it saturates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Field {
+    pub(crate) agc: u8,
+    pub(crate) magnitude: u16,
+    pub(crate) detected: bool,
+    pub(crate) too_weak: bool,
+    pub(crate) too_strong: bool,
+}
+
+impl Field {
+    /// Air gap in mm at which the AGC is centered in its range.
+    const NOMINAL_DISTANCE: f32 = 1.5;
+    /// Air gap in mm beyond which no magnet is detected.
+    const DETECTION_LIMIT: f32 = 4.0;
+    /// Magnitude at the nominal air gap.
+    const NOMINAL_MAGNITUDE: f32 = 2048.0;
+
+    pub(crate) fn at_distance(distance: f32, agc_range: u8) -> Self {
+        let distance = distance.max(0.01);
+        let agc_range = f32::from(agc_range);
+        let ideal_agc = agc_range / 2.0 * distance / Self::NOMINAL_DISTANCE;
+        let agc = ideal_agc.clamp(0.0, agc_range);
+        let strength = libm::powf(Self::NOMINAL_DISTANCE / distance, 3.0);
+        let gain = agc.max(1.0) / (agc_range / 2.0);
+        let magnitude = (Self::NOMINAL_MAGNITUDE * strength * gain).clamp(0.0, 4095.0);
+        Self {
+            agc: agc as u8,
+            magnitude: magnitude as u16,
+            detected: distance <= Self::DETECTION_LIMIT,
+            too_weak: ideal_agc > agc_range,
+            too_strong: ideal_agc < agc_range / 16.0,
+        }
+    }
+}
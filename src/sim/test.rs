@@ -0,0 +1,345 @@
+use core::time::Duration;
+
+use embedded_hal::i2c::I2c;
+use embedded_hal_mock::eh1::delay::NoopDelay;
+
+use crate::{
+    angle::Angle,
+    configuration::{Configuration, FastFilterThreshold, Hysteresis},
+    error::Error,
+    magnet::{Placement, SupplyVoltage},
+    sim::{self, Motion, SimulatedAs5600},
+    status::{Status, StatusFlags},
+    As5600,
+};
+
+#[test]
+fn reports_raw_and_scaled_angle() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_raw_angle(Angle::from_counts(1500));
+    let mut as5600 = As5600::new(sim);
+    assert_eq!(Angle::from_counts(1500), as5600.raw_angle().unwrap());
+    assert_eq!(Angle::from_counts(1500), as5600.angle().unwrap());
+
+    // Map a quarter turn starting at 1000 to the full output range.
+    as5600.set_zero_position(Angle::from_counts(1000)).unwrap();
+    as5600
+        .set_maximum_position(Angle::from_counts(2024))
+        .unwrap();
+    assert_eq!(Angle::from_counts(2000), as5600.angle().unwrap());
+    assert_eq!(Angle::from_counts(1500), as5600.raw_angle().unwrap());
+
+    // Outside of the range, the output saturates at the nearer end.
+    let mut sim = as5600.release();
+    sim.set_raw_angle(Angle::from_counts(2100));
+    assert_eq!(Angle::MAX, sim.angle());
+    sim.set_raw_angle(Angle::from_counts(900));
+    assert_eq!(Angle::ZERO, sim.angle());
+}
+
+#[test]
+fn maximum_angle_defines_range() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_raw_angle(Angle::from_counts(512));
+    let mut as5600 = As5600::new(sim);
+    as5600.set_maximum_angle(Angle::from_counts(2048)).unwrap();
+    assert_eq!(Angle::from_counts(1024), as5600.angle().unwrap());
+}
+
+#[test]
+fn applies_hysteresis() {
+    let mut as5600 = As5600::new(SimulatedAs5600::new());
+    let mut config = as5600.config().unwrap();
+    config.hysteresis = Hysteresis::Lsb2;
+    as5600.set_config(config).unwrap();
+    let mut sim = as5600.release();
+    sim.set_raw_angle(Angle::from_counts(2));
+    assert_eq!(Angle::ZERO, sim.angle());
+    sim.set_raw_angle(Angle::from_counts(3));
+    assert_eq!(Angle::from_counts(3), sim.angle());
+    sim.set_raw_angle(Angle::from_counts(1));
+    assert_eq!(Angle::from_counts(3), sim.angle());
+}
+
+#[test]
+fn preserves_factory_config_bits() {
+    let sim = SimulatedAs5600::new().with_factory_config_bits(0b0100_0000_0000_0000);
+    let mut as5600 = As5600::new(sim);
+    let config: Configuration = as5600.config().unwrap();
+    as5600.set_config(config).unwrap();
+    let sim = as5600.release();
+    assert!(sim.factory_config_bits_intact());
+    assert_eq!(0b0100_0000_0000_0000, sim.config());
+}
+
+#[test]
+fn counts_angle_burns() {
+    let mut delay = NoopDelay;
+    let mut as5600 = As5600::new(SimulatedAs5600::new());
+    as5600.set_zero_position(Angle::from_counts(100)).unwrap();
+    for zmco in 1..=3 {
+        as5600.persist_position_settings(&mut delay).unwrap();
+        assert_eq!(zmco, as5600.zmco().unwrap());
+    }
+    assert_eq!(
+        Err(Error::MaximumPositionPersistsReached),
+        as5600.persist_position_settings(&mut delay)
+    );
+    as5600.set_zero_position(Angle::from_counts(200)).unwrap();
+    let mut sim = as5600.release();
+    assert_eq!(Angle::from_counts(100), sim.otp().zero_position);
+    sim.power_cycle();
+    assert_eq!(Angle::from_counts(100), sim.zero_position());
+}
+
+#[test]
+fn burn_requires_magnet() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_status(StatusFlags::MAGNET_LOW);
+    let mut as5600 = As5600::new(sim);
+    assert_eq!(
+        Err(Error::MagnetRequired),
+        as5600.persist_position_settings(&mut NoopDelay)
+    );
+    let mut sim = as5600.release();
+    // The device itself also ignores the command.
+    sim.write(0x36, &[0xFF, 0x80]).unwrap();
+    assert_eq!(0, sim.zmco());
+    sim.set_status(StatusFlags::MAGNET_DETECTED);
+    assert_eq!(Ok(Status::MagnetDetected), As5600::new(sim).magnet_status());
+}
+
+#[test]
+fn settings_burn_only_once() {
+    let mut delay = NoopDelay;
+    let mut as5600 = As5600::new(SimulatedAs5600::new());
+    as5600.set_maximum_angle(Angle::from_counts(1000)).unwrap();
+    as5600
+        .persist_maximum_angle_and_config_settings(&mut delay)
+        .unwrap();
+    let mut sim = as5600.release();
+    assert_eq!(Angle::from_counts(1000), sim.otp().maximum_angle);
+    sim.write(0x36, &[0x05, 0x01, 0x00]).unwrap();
+    sim.write(0x36, &[0xFF, 0x40]).unwrap();
+    assert_eq!(Angle::from_counts(1000), sim.otp().maximum_angle);
+}
+
+#[test]
+fn load_otp_restores_shadow_registers() {
+    let mut sim = SimulatedAs5600::new();
+    sim.write(0x36, &[0x01, 0x00, 0x64]).unwrap();
+    sim.write(0x36, &[0xFF, 0x80]).unwrap();
+    sim.write(0x36, &[0x01, 0x02, 0x00]).unwrap();
+    assert_eq!(Angle::from_counts(0x200), sim.zero_position());
+    for command in [0x01, 0x11, 0x10] {
+        sim.write(0x36, &[0xFF, command]).unwrap();
+    }
+    assert_eq!(Angle::from_counts(100), sim.zero_position());
+}
+
+#[test]
+fn pointer_auto_increments() {
+    let mut sim = SimulatedAs5600::new();
+    sim.write(0x36, &[0x01, 0x01, 0x23, 0x04, 0x56]).unwrap();
+    let mut buffer = [0u8; 4];
+    sim.write_read(0x36, &[0x01], &mut buffer).unwrap();
+    assert_eq!([0x01, 0x23, 0x04, 0x56], buffer);
+    assert_eq!(0x05, sim.pointer());
+}
+
+#[test]
+fn pointer_stays_on_angle_registers() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_raw_angle(Angle::from_counts(0x0123));
+    let mut buffer = [0u8; 2];
+    sim.write_read(0x36, &[0x0E], &mut buffer).unwrap();
+    assert_eq!([0x01, 0x23], buffer);
+    sim.set_raw_angle(Angle::from_counts(0x0456));
+    sim.read(0x36, &mut buffer).unwrap();
+    assert_eq!([0x04, 0x56], buffer);
+    assert_eq!(0x0E, sim.pointer());
+
+    // Reaching the register by auto-increment does not lock the pointer.
+    let mut buffer = [0u8; 3];
+    sim.write_read(0x36, &[0x1A], &mut buffer).unwrap();
+    assert_eq!(0x1D, sim.pointer());
+}
+
+#[test]
+fn ignores_other_addresses() {
+    let mut sim = SimulatedAs5600::new();
+    assert_eq!(
+        Err(sim::Error::NoAcknowledge(0x40)),
+        sim.write(0x40, &[0x01, 0x00])
+    );
+}
+
+#[test]
+fn follows_constant_speed_motion() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_tick(Duration::from_millis(15));
+    sim.set_motion(Motion::ConstantSpeed {
+        start: Angle::from_counts(3900),
+        counts_per_second: 10_000.0,
+    });
+    let mut as5600 = As5600::new(sim);
+    let first = as5600.raw_angle().unwrap();
+    let second = as5600.raw_angle().unwrap();
+    assert_eq!(150, second.shortest_difference(first));
+    assert!(second < first, "motion wraps around");
+}
+
+#[test]
+fn slow_filter_lags_behind_step() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_motion(Motion::Step {
+        from: Angle::ZERO,
+        to: Angle::from_counts(1000),
+        at: Duration::from_millis(1),
+    });
+    sim.advance(Duration::from_millis(2));
+    assert_eq!(Angle::from_counts(1000), sim.raw_angle());
+    assert!(sim.angle().counts() < 1000 && sim.angle().counts() > 0);
+    sim.advance(Duration::from_millis(3));
+    assert!(
+        sim.angle()
+            .shortest_difference(Angle::from_counts(1000))
+            .abs()
+            <= 10
+    );
+}
+
+#[test]
+fn fast_filter_settles_quickly() {
+    let mut as5600 = As5600::new(SimulatedAs5600::new());
+    let mut config = as5600.config().unwrap();
+    config.fast_filter_threshold = FastFilterThreshold::Lsb6;
+    as5600.set_config(config).unwrap();
+    let mut sim = as5600.release();
+    sim.set_motion(Motion::Step {
+        from: Angle::ZERO,
+        to: Angle::from_counts(1000),
+        at: Duration::ZERO,
+    });
+    sim.advance(Duration::from_micros(600));
+    assert!(
+        sim.angle()
+            .shortest_difference(Angle::from_counts(1000))
+            .abs()
+            <= 10
+    );
+}
+
+#[test]
+fn plays_back_sine_and_trace() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_motion(Motion::Sine {
+        center: Angle::from_counts(2048),
+        amplitude: 1000.0,
+        frequency: 10.0,
+    });
+    sim.advance(Duration::from_micros(25_050));
+    assert_eq!(Angle::from_counts(3048), sim.raw_angle());
+
+    let samples = vec![Angle::from_counts(10), Angle::from_counts(20)];
+    sim.set_motion(Motion::Trace {
+        samples,
+        interval: Duration::from_millis(1),
+    });
+    sim.advance(Duration::from_micros(500));
+    assert_eq!(Angle::from_counts(10), sim.raw_angle());
+    sim.advance(Duration::from_millis(5));
+    assert_eq!(Angle::from_counts(20), sim.raw_angle());
+}
+
+#[test]
+fn adds_deterministic_noise() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_raw_angle(Angle::from_counts(2000));
+    sim.set_noise(2.0, 42);
+    let mut other = sim.clone();
+    let mut deviation = 0;
+    for _ in 0..100 {
+        sim.advance(Duration::from_micros(150));
+        other.advance(Duration::from_micros(150));
+        assert_eq!(sim.raw_angle(), other.raw_angle());
+        deviation += sim
+            .raw_angle()
+            .shortest_difference(Angle::from_counts(2000))
+            .abs();
+    }
+    assert!(deviation > 50 && deviation < 400);
+}
+
+#[test]
+fn distance_drives_magnet_registers() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_distance(1.5);
+    let mut as5600 = As5600::new(sim);
+    let quality = as5600.magnet_quality(SupplyVoltage::V5).unwrap();
+    assert_eq!(Placement::Good, quality.placement());
+    assert_eq!(2048, quality.magnitude);
+
+    let mut sim = as5600.release();
+    sim.set_distance(3.5);
+    let flags = As5600::new(sim.clone()).magnet_status_flags().unwrap();
+    assert!(flags.magnet_detected() && flags.too_weak());
+    sim.set_distance(0.1);
+    let mut as5600 = As5600::new(sim);
+    assert_eq!(Status::MagnetDetectedHigh, as5600.magnet_status().unwrap());
+    let mut sim = as5600.release();
+    sim.set_distance(5.0);
+    assert_eq!(Status::MagnetLow, As5600::new(sim).magnet_status().unwrap());
+}
+
+#[test]
+fn snapshot_matches_getters() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_raw_angle(Angle::from_counts(3000));
+    let mut as5600 = As5600::new(sim);
+    as5600.set_zero_position(Angle::from_counts(1000)).unwrap();
+    let snapshot = as5600.read_all().unwrap();
+    assert_eq!(as5600.zero_position().unwrap(), snapshot.zero_position);
+    assert_eq!(as5600.raw_angle().unwrap(), snapshot.raw_angle);
+    assert_eq!(as5600.angle().unwrap(), snapshot.angle);
+    assert_eq!(as5600.magnitude().unwrap(), snapshot.magnitude);
+    assert_eq!(as5600.config().unwrap(), snapshot.config().unwrap());
+}
+
+#[test]
+fn telemetry_matches_getters() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_raw_angle(Angle::from_counts(1234));
+    sim.set_distance(2.0);
+    let mut as5600 = As5600::new(sim);
+    let telemetry = as5600.read_telemetry().unwrap();
+    assert_eq!(as5600.magnet_status_flags().unwrap(), telemetry.status);
+    assert_eq!(Angle::from_counts(1234), telemetry.raw_angle);
+    assert_eq!(Angle::from_counts(1234), telemetry.angle);
+    assert_eq!(
+        as5600.automatic_gain_control().unwrap(),
+        telemetry.automatic_gain_control
+    );
+    assert_eq!(as5600.magnitude().unwrap(), telemetry.magnitude);
+}
+
+#[test]
+fn streams_moving_angle() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_tick(Duration::from_micros(1500));
+    sim.set_motion(Motion::ConstantSpeed {
+        start: Angle::ZERO,
+        counts_per_second: 10_000.0,
+    });
+    let mut as5600 = As5600::new(sim);
+    as5600.set_streaming(true);
+    let mut last = as5600.raw_angle().unwrap();
+    for _ in 0..5 {
+        let angle = as5600.raw_angle().unwrap();
+        assert_eq!(15, angle.shortest_difference(last));
+        last = angle;
+    }
+    // The write is a transaction of its own, the re-primed read is another one.
+    as5600.set_zero_position(Angle::from_counts(0)).unwrap();
+    assert_eq!(30, as5600.raw_angle().unwrap().shortest_difference(last));
+}
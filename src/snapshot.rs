@@ -0,0 +1,197 @@
+use crate::angle::Angle;
+use crate::configuration::{self, Configuration, RawConfiguration};
+use crate::status::StatusFlags;
+
+/// Contents of all registers from `ZMCO` through `MAGNITUDE`, as read.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegisterSnapshot {
+    /// Register `ZMCO`.
+    pub zmco: u8,
+    /// Register `ZPOS`.
+    pub zero_position: Angle,
+    /// Register `MPOS`.
+    pub maximum_position: Angle,
+    /// Register `MANG`.
+    pub maximum_angle: Angle,
+    /// Register `CONF`, unparsed. See [`RegisterSnapshot::config`].
+    pub config: u16,
+    /// Register `STATUS`.
+    pub status: StatusFlags,
+    /// Register `RAW_ANGLE`.
+    pub raw_angle: Angle,
+    /// Register `ANGLE`.
+    pub angle: Angle,
+    /// Register `AGC`.
+    pub automatic_gain_control: u8,
+    /// Register `MAGNITUDE`.
+    pub magnitude: u16,
+}
+
+/// Output registers which change while the magnet moves.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Telemetry {
+    /// Register `STATUS`.
+    pub status: StatusFlags,
+    /// Register `RAW_ANGLE`.
+    pub raw_angle: Angle,
+    /// Register `ANGLE`.
+    pub angle: Angle,
+    /// Register `AGC`.
+    pub automatic_gain_control: u8,
+    /// Register `MAGNITUDE`.
+    pub magnitude: u16,
+}
+
+impl Telemetry {
+    /// Parse the bytes of the burst reads starting at `STATUS` and `AGC`.
+    pub(crate) fn from_bytes(output: [u8; 5], magnet: [u8; 3]) -> Self {
+        Self {
+            status: StatusFlags::from(output[0]),
+            raw_angle: Angle::from_counts(u16::from_be_bytes([output[1], output[2]])),
+            angle: Angle::from_counts(u16::from_be_bytes([output[3], output[4]])),
+            automatic_gain_control: magnet[0],
+            magnitude: u16::from_be_bytes([magnet[1], magnet[2]]) & 0x0FFF,
+        }
+    }
+}
+
+/// Register contained in a [`RegisterSnapshot`] or verified after a burn.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Field {
+    /// Register `ZMCO`.
+    Zmco,
+    /// Register `ZPOS`.
+    ZeroPosition,
+    /// Register `MPOS`.
+    MaximumPosition,
+    /// Register `MANG`.
+    MaximumAngle,
+    /// Register `CONF`.
+    Config,
+    /// Register `STATUS`.
+    Status,
+    /// Register `RAW_ANGLE`.
+    RawAngle,
+    /// Register `ANGLE`.
+    Angle,
+    /// Register `AGC`.
+    AutomaticGainControl,
+    /// Register `MAGNITUDE`.
+    Magnitude,
+}
+
+/// Register which differs between two snapshots.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Change {
+    /// Differing register.
+    pub field: Field,
+    /// Value in the first snapshot.
+    pub before: u16,
+    /// Value in the second snapshot.
+    pub after: u16,
+}
+
+impl RegisterSnapshot {
+    /// Parse register `CONF`.
+    pub fn config(&self) -> Result<Configuration, configuration::error::Error> {
+        Configuration::try_from(self.config)
+    }
+
+    /// Parse register `CONF` leniently, see [`RawConfiguration`].
+    pub fn raw_config(&self) -> RawConfiguration {
+        RawConfiguration::from(self.config)
+    }
+
+    /// Registers which differ between `self` and `other`, in register order.
+    pub fn diff<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = Change> + 'a {
+        self.fields()
+            .into_iter()
+            .zip(other.fields())
+            .filter(|((_, before), (_, after))| before != after)
+            .map(|((field, before), (_, after))| Change {
+                field,
+                before,
+                after,
+            })
+    }
+
+    /// Parse the bytes of the burst reads starting at `ZMCO`, `STATUS` and `AGC`.
+    pub(crate) fn from_bytes(settings: [u8; 9], output: [u8; 5], magnet: [u8; 3]) -> Self {
+        let word = |high: u8, low: u8| u16::from_be_bytes([high, low]);
+        let telemetry = Telemetry::from_bytes(output, magnet);
+        Self {
+            zmco: settings[0] & 0b0000_0011,
+            zero_position: Angle::from_counts(word(settings[1], settings[2])),
+            maximum_position: Angle::from_counts(word(settings[3], settings[4])),
+            maximum_angle: Angle::from_counts(word(settings[5], settings[6])),
+            config: word(settings[7], settings[8]),
+            status: telemetry.status,
+            raw_angle: telemetry.raw_angle,
+            angle: telemetry.angle,
+            automatic_gain_control: telemetry.automatic_gain_control,
+            magnitude: telemetry.magnitude,
+        }
+    }
+
+    fn fields(&self) -> [(Field, u16); 10] {
+        [
+            (Field::Zmco, u16::from(self.zmco)),
+            (Field::ZeroPosition, self.zero_position.counts()),
+            (Field::MaximumPosition, self.maximum_position.counts()),
+            (Field::MaximumAngle, self.maximum_angle.counts()),
+            (Field::Config, self.config),
+            (Field::Status, u16::from(self.status.bits())),
+            (Field::RawAngle, self.raw_angle.counts()),
+            (Field::Angle, self.angle.counts()),
+            (
+                Field::AutomaticGainControl,
+                u16::from(self.automatic_gain_control),
+            ),
+            (Field::Magnitude, self.magnitude),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::angle::Angle;
+    use crate::snapshot::{Change, Field, RegisterSnapshot};
+
+    #[test]
+    fn diffs_changed_registers() {
+        let before = RegisterSnapshot::from_bytes(
+            [0x01, 0x00, 0x64, 0x04, 0x4C, 0x00, 0x00, 0x00, 0x00],
+            [0x20, 0x02, 0x00, 0x02, 0x00],
+            [0x80, 0x08, 0x00],
+        );
+        assert_eq!(Angle::from_counts(1100), before.maximum_position);
+        assert_eq!(0x0800, before.magnitude);
+        assert_eq!(None, before.diff(&before).next());
+
+        let mut after = before;
+        after.raw_angle = Angle::from_counts(0x210);
+        after.automatic_gain_control = 0x7F;
+        let mut changes = before.diff(&after);
+        assert_eq!(
+            Some(Change {
+                field: Field::RawAngle,
+                before: 0x200,
+                after: 0x210
+            }),
+            changes.next()
+        );
+        assert_eq!(
+            Some(Field::AutomaticGainControl),
+            changes.next().map(|change| change.field)
+        );
+        assert_eq!(None, changes.next());
+    }
+}
@@ -16,6 +16,7 @@ pub enum Error {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, FromPrimitive)]
 #[cfg_attr(test, derive(Arbitrary))]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum Status {
     /// Magnet too close.
@@ -35,7 +36,16 @@ impl TryFrom<u8> for Status {
 
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         // See datasheet Fig. 21, section "Status Registers".
-        FromPrimitive::from_u8(value & 0b0011_1000).ok_or(Error::InvalidBitPattern(value))
+        FromPrimitive::from_u8(value & StatusFlags::ALL.bits())
+            .ok_or(Error::InvalidBitPattern(value))
+    }
+}
+
+impl TryFrom<StatusFlags> for Status {
+    type Error = Error;
+
+    fn try_from(flags: StatusFlags) -> Result<Self, Self::Error> {
+        Self::try_from(flags.bits())
     }
 }
 
@@ -53,9 +63,111 @@ impl From<Status> for u8 {
     }
 }
 
+/// Individual magnet detection flags of register `STATUS`.
+///
+/// Unlike [`Status`], every register value is representable, including combinations
+/// the datasheet does not list. The raw byte is preserved as read.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct StatusFlags(u8);
+
+impl StatusFlags {
+    /// `MH`: AGC minimum gain overflow, magnet too strong.
+    pub const MAGNET_HIGH: Self = Self(0b0000_1000);
+    /// `ML`: AGC maximum gain overflow, magnet too weak.
+    pub const MAGNET_LOW: Self = Self(0b0001_0000);
+    /// `MD`: Magnet was detected.
+    pub const MAGNET_DETECTED: Self = Self(0b0010_0000);
+    /// All defined flags.
+    pub const ALL: Self = Self(0b0011_1000);
+
+    /// Create flags from a raw register value, retaining undefined bits.
+    pub const fn from_bits_retain(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    /// Get the raw register value, including undefined bits.
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Whether all flags in `other` are set.
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether a magnet was detected (`MD`).
+    pub const fn magnet_detected(&self) -> bool {
+        self.contains(Self::MAGNET_DETECTED)
+    }
+
+    /// Whether the magnet is too weak (`ML`).
+    pub const fn too_weak(&self) -> bool {
+        self.contains(Self::MAGNET_LOW)
+    }
+
+    /// Whether the magnet is too strong (`MH`).
+    pub const fn too_strong(&self) -> bool {
+        self.contains(Self::MAGNET_HIGH)
+    }
+
+    /// Interpret the flags as one of the documented [`Status`] combinations.
+    pub fn status(&self) -> Result<Status, Error> {
+        Status::try_from(*self)
+    }
+}
+
+impl core::fmt::Debug for StatusFlags {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StatusFlags")
+            .field("bits", &format_args!("{:#04x}", self.0))
+            .field("magnet_detected", &self.magnet_detected())
+            .field("too_weak", &self.too_weak())
+            .field("too_strong", &self.too_strong())
+            .finish()
+    }
+}
+
+impl From<u8> for StatusFlags {
+    fn from(value: u8) -> Self {
+        Self::from_bits_retain(value)
+    }
+}
+
+impl From<[u8; 1]> for StatusFlags {
+    fn from(value: [u8; 1]) -> Self {
+        Self::from_bits_retain(value[0])
+    }
+}
+
+impl From<StatusFlags> for u8 {
+    fn from(flags: StatusFlags) -> Self {
+        flags.0
+    }
+}
+
+impl From<Status> for StatusFlags {
+    fn from(status: Status) -> Self {
+        Self(u8::from(status))
+    }
+}
+
+impl core::ops::BitOr for StatusFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::status::Status;
+    use crate::status::{Status, StatusFlags};
     use proptest::prelude::*;
 
     proptest! {
@@ -65,5 +177,27 @@ mod test {
             let roundtrip = Status::try_from(byte).unwrap();
             assert_eq!(status, roundtrip);
         }
+
+        #[test]
+        fn flags_preserve_raw_byte(byte in any::<u8>()) {
+            let flags = StatusFlags::from(byte);
+            assert_eq!(byte, u8::from(flags));
+            if let Ok(status) = flags.status() {
+                assert_eq!(StatusFlags::from(status).bits(), byte & StatusFlags::ALL.bits());
+            }
+        }
+    }
+
+    #[test]
+    fn decodes_undocumented_combinations() {
+        let flags = StatusFlags::from(0x38);
+        assert!(flags.magnet_detected() && flags.too_weak() && flags.too_strong());
+        assert!(flags.status().is_err());
+        let flags = StatusFlags::from(0x18);
+        assert!(!flags.magnet_detected() && flags.too_weak() && flags.too_strong());
+        assert_eq!(
+            StatusFlags::MAGNET_DETECTED | StatusFlags::MAGNET_LOW,
+            StatusFlags::from(Status::MagnetDetectedLow)
+        );
     }
 }
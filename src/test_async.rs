@@ -0,0 +1,91 @@
+use core::future::Future;
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+
+#[cfg(feature = "as5600l")]
+use crate::{
+    angle::Angle,
+    asynch::As5600,
+    burn::{BurnContents, BurnOutcome, BurnPolicy},
+    error::Error,
+};
+#[cfg(feature = "as5600l")]
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+
+/// Run a future which never waits, as mocked and simulated buses complete every transaction
+/// at once.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future is pending"),
+    }
+}
+
+#[cfg(feature = "as5600l")]
+#[test]
+fn set_address() {
+    let i2c = Mock::new(&[
+        Transaction::write(0x40, vec![0x20, 0x50]),
+        Transaction::write(0x40, vec![0x21, 0x50]),
+        Transaction::write_read(0x28, vec![0x0C], vec![0x06, 0x70]),
+    ]);
+    let mut as5600 = As5600::with_address(0x40, i2c);
+    block_on(as5600.set_address(0x28)).unwrap();
+    assert_eq!(
+        block_on(as5600.raw_angle()).unwrap(),
+        Angle::from_counts(0x0670)
+    );
+    as5600.release().done();
+}
+
+#[cfg(feature = "as5600l")]
+#[test]
+fn set_address_rejects_reserved_addresses() {
+    let i2c = Mock::new(&[]);
+    let mut as5600 = As5600::with_address(0x40, i2c);
+    for address in [0x00, 0x07, 0x78, 0xFF] {
+        assert_eq!(
+            block_on(as5600.set_address(address)).unwrap_err(),
+            Error::InvalidAddress
+        );
+    }
+    as5600.release().done();
+}
+
+#[cfg(feature = "as5600l")]
+#[test]
+fn persist_address() {
+    let i2c = Mock::new(&[
+        Transaction::write(0x40, vec![0x20, 0x50]),
+        Transaction::write(0x40, vec![0x21, 0x50]),
+        Transaction::write(0x28, vec![0xFF, 0x40]),
+    ]);
+    let mut as5600 = As5600::with_address(0x40, i2c);
+    block_on(as5600.set_address(0x28)).unwrap();
+    assert_eq!(
+        block_on(as5600.persist_address()).unwrap(),
+        BurnOutcome::Burned(0x40)
+    );
+    as5600.release().done();
+}
+
+#[cfg(feature = "as5600l")]
+#[test]
+fn persist_address_follows_burn_policy() {
+    let i2c = Mock::new(&[Transaction::write_read(0x40, vec![0x20], vec![0x50])]);
+    let mut as5600 = As5600::with_address(0x40, i2c);
+    as5600.set_burn_policy(BurnPolicy::DryRun);
+    assert_eq!(
+        block_on(as5600.persist_address()).unwrap(),
+        BurnOutcome::DryRun {
+            command: 0x40,
+            intended: BurnContents::Address { address: 0x28 }
+        }
+    );
+    as5600.set_burn_policy(BurnPolicy::Deny);
+    assert_eq!(
+        block_on(as5600.persist_address()).unwrap_err(),
+        Error::BurnDenied
+    );
+    as5600.release().done();
+}
@@ -0,0 +1,219 @@
+//! The blocking and the async driver must produce identical bus traffic.
+
+use alloc::vec::Vec;
+
+use embedded_hal::i2c::{ErrorType, Operation};
+use embedded_hal_mock::eh1::delay::NoopDelay;
+
+use crate::angle::Angle;
+use crate::asynch;
+use crate::burn::{BurnKind, BurnPolicy};
+use crate::configuration::{
+    Configuration, FastFilterThreshold, Hysteresis, OutputStage, PowerMode, PwmFreq,
+    SlowFilterMode, WatchdogState,
+};
+use crate::magnet::SupplyVoltage;
+use crate::profile::Profile;
+use crate::register::Register;
+use crate::sim::{self, SimulatedAs5600};
+use crate::test_async::block_on;
+use crate::As5600;
+
+/// Transfer of a transaction, with the bytes written or read.
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Transfer {
+    Write(Vec<u8>),
+    Read(Vec<u8>),
+}
+
+/// Simulated device which logs every transaction.
+#[derive(Debug, PartialEq, Clone)]
+struct Traced {
+    device: SimulatedAs5600,
+    traffic: Vec<(u8, Vec<Transfer>)>,
+}
+
+impl Traced {
+    fn new() -> Self {
+        let mut device = SimulatedAs5600::new();
+        device.set_raw_angle(Angle::from_counts(1000));
+        Self {
+            device,
+            traffic: Vec::new(),
+        }
+    }
+
+    fn execute(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), sim::Error> {
+        let result = embedded_hal::i2c::I2c::transaction(&mut self.device, address, operations);
+        let transfers = operations
+            .iter()
+            .map(|operation| match operation {
+                Operation::Write(bytes) => Transfer::Write(bytes.to_vec()),
+                Operation::Read(bytes) => Transfer::Read(bytes.to_vec()),
+            })
+            .collect();
+        self.traffic.push((address, transfers));
+        result
+    }
+}
+
+impl ErrorType for Traced {
+    type Error = sim::Error;
+}
+
+impl embedded_hal::i2c::I2c for Traced {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.execute(address, operations)
+    }
+}
+
+impl embedded_hal_async::i2c::I2c for Traced {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.execute(address, operations)
+    }
+}
+
+/// Call an operation twice on each driver, after the given setup calls, and compare the
+/// results and the bus traffic.
+macro_rules! assert_parity {
+    ($method:ident($($arg:expr),*)) => {
+        assert_parity!(with []; $method($($arg),*))
+    };
+    (with [$($setup:ident($($setup_arg:expr),*)),*]; $method:ident($($arg:expr),*)) => {{
+        let mut blocking = As5600::new(Traced::new());
+        let mut asynchronous = asynch::As5600::new(Traced::new());
+        $(
+            blocking.$setup($($setup_arg),*);
+            asynchronous.$setup($($setup_arg),*);
+        )*
+        for _ in 0..2 {
+            let expected = blocking.$method($($arg),*);
+            let actual = block_on(asynchronous.$method($($arg),*));
+            assert_eq!(expected, actual, stringify!($method));
+        }
+        let expected = blocking.release();
+        let actual = asynchronous.release();
+        assert_eq!(expected.traffic, actual.traffic, stringify!($method));
+        assert_eq!(expected.device, actual.device, stringify!($method));
+    }};
+}
+
+#[test]
+fn reads_match() {
+    assert_parity!(raw_angle());
+    assert_parity!(angle());
+    assert_parity!(zmco());
+    assert_parity!(magnet_status());
+    assert_parity!(magnet_status_flags());
+    assert_parity!(zero_position());
+    assert_parity!(maximum_position());
+    assert_parity!(maximum_angle());
+    assert_parity!(config());
+    assert_parity!(raw_config());
+    assert_parity!(automatic_gain_control());
+    assert_parity!(magnitude());
+    assert_parity!(magnet_quality(SupplyVoltage::V3_3));
+    assert_parity!(read_all());
+    assert_parity!(read_telemetry());
+    assert_parity!(reload_otp());
+}
+
+#[test]
+fn streaming_reads_match() {
+    assert_parity!(with [set_streaming(true)]; raw_angle());
+    assert_parity!(with [set_streaming(true)]; angle());
+    assert_parity!(with [set_streaming(true)]; magnitude());
+    assert_parity!(with [set_streaming(true)]; read_telemetry());
+}
+
+#[test]
+fn writes_match() {
+    assert_parity!(set_zero_position(Angle::from_counts(100)));
+    assert_parity!(set_maximum_position(Angle::from_counts(1100)));
+    assert_parity!(set_maximum_angle(Angle::from_counts(1024)));
+    assert_parity!(set_config(Configuration::low_power()));
+    assert_parity!(set_power_mode(PowerMode::Lpm3));
+    assert_parity!(set_hysteresis(Hysteresis::Lsb2));
+    assert_parity!(set_output_stage(OutputStage::DigitalPwm));
+    assert_parity!(set_pwm_frequency(PwmFreq::PwmF4));
+    assert_parity!(set_slow_filter(SlowFilterMode::X2));
+    assert_parity!(set_fast_filter_threshold(FastFilterThreshold::Lsb10));
+    assert_parity!(set_watchdog(WatchdogState::On));
+}
+
+#[test]
+fn burns_match() {
+    for policy in [BurnPolicy::Allow, BurnPolicy::Deny, BurnPolicy::DryRun] {
+        assert_parity!(with [set_burn_policy(policy)]; persist_position_settings(&mut NoopDelay));
+        assert_parity!(
+            with [set_burn_policy(policy)];
+            persist_maximum_angle_and_config_settings(&mut NoopDelay)
+        );
+        let profile = Profile {
+            burn: true,
+            ..Profile::with_maximum_angle(
+                Angle::from_counts(100),
+                Angle::from_counts(1024),
+                Configuration::low_noise(),
+            )
+        };
+        assert_parity!(with [set_burn_policy(policy)]; apply_profile(&profile, &mut NoopDelay));
+    }
+}
+
+#[test]
+fn burn_sessions_match() {
+    for kind in [BurnKind::Angle, BurnKind::Setting] {
+        let mut blocking = As5600::new(Traced::new());
+        let mut asynchronous = asynch::As5600::new(Traced::new());
+        let expected = blocking
+            .burn_session(kind)
+            .and_then(|session| session.execute(&mut NoopDelay));
+        let actual = block_on(async {
+            asynchronous
+                .burn_session(kind)
+                .await?
+                .execute(&mut NoopDelay)
+                .await
+        });
+        assert_eq!(expected, actual);
+        assert_eq!(blocking.release(), asynchronous.release());
+    }
+}
+
+#[test]
+fn register_reads_match() {
+    let mut blocking = As5600::new(Traced::new());
+    let mut asynchronous = asynch::As5600::new(Traced::new());
+    for (start, len) in [
+        (Register::Zmco, 9),
+        (Register::Status, 5),
+        (Register::Angle, 3),
+    ] {
+        let mut expected = [0; 9];
+        let mut actual = [0; 9];
+        assert_eq!(
+            blocking.read_registers(start, &mut expected[..len]),
+            block_on(asynchronous.read_registers(start, &mut actual[..len]))
+        );
+        assert_eq!(expected, actual);
+    }
+    assert_eq!(blocking.release(), asynchronous.release());
+}
+
+#[cfg(feature = "as5600l")]
+#[test]
+fn address_management_matches() {
+    assert_parity!(set_address(0x40));
+    assert_parity!(set_address(0x78));
+    assert_parity!(persist_address());
+    assert_parity!(with [set_burn_policy(BurnPolicy::Deny)]; persist_address());
+}
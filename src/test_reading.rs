@@ -1,10 +1,15 @@
 use crate::{
+    angle::Angle,
+    burn::OtpSnapshot,
     configuration::{
         Configuration, FastFilterThreshold, Hysteresis, OutputStage, PowerMode, PwmFreq,
         SlowFilterMode, WatchdogState,
     },
     error,
-    status::{self, Status},
+    magnet::{Placement, SupplyVoltage},
+    register::Register,
+    snapshot::{RegisterSnapshot, Telemetry},
+    status::{self, Status, StatusFlags},
     As5600,
 };
 use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
@@ -69,7 +74,10 @@ fn reads_zero_position_register() {
         vec![0b1001_1010, 0b1010_1111],
     )]);
     let mut as5600 = As5600::new(i2c);
-    assert_eq!(0b0000_1010_1010_1111, as5600.zero_position().unwrap());
+    assert_eq!(
+        Angle::from_counts(0b0000_1010_1010_1111),
+        as5600.zero_position().unwrap()
+    );
     as5600.release().done();
 }
 
@@ -81,7 +89,10 @@ fn reads_maximum_position_register() {
         vec![0b1101_0010, 0b0010_1010],
     )]);
     let mut as5600 = As5600::new(i2c);
-    assert_eq!(0b0000_0010_0010_1010, as5600.maximum_position().unwrap());
+    assert_eq!(
+        Angle::from_counts(0b0000_0010_0010_1010),
+        as5600.maximum_position().unwrap()
+    );
     as5600.release().done();
 }
 
@@ -93,7 +104,10 @@ fn reads_maximum_angle_register() {
         vec![0b0001_1110, 0b1010_1011],
     )]);
     let mut as5600 = As5600::new(i2c);
-    assert_eq!(0b0000_1110_1010_1011, as5600.maximum_angle().unwrap());
+    assert_eq!(
+        Angle::from_counts(0b0000_1110_1010_1011),
+        as5600.maximum_angle().unwrap()
+    );
     as5600.release().done();
 }
 
@@ -104,17 +118,19 @@ fn reads_config_register() {
         vec![0x07],
         vec![0b1110_0011, 0b1010_1100],
     )]);
-    let expected_config = Configuration {
-        power_mode: PowerMode::Nom,
-        hysteresis: Hysteresis::Lsb3,
-        output_stage: OutputStage::DigitalPwm,
-        pwm_frequency: PwmFreq::PwmF3,
-        slow_filter: SlowFilterMode::X2,
-        fast_filter_threshold: FastFilterThreshold::SlowFilterOnly,
-        watchdog_state: WatchdogState::On,
-    };
+    let expected_config = Configuration::builder()
+        .power_mode(PowerMode::Nom)
+        .hysteresis(Hysteresis::Lsb3)
+        .output_stage(OutputStage::DigitalPwm)
+        .pwm_frequency(PwmFreq::PwmF3)
+        .slow_filter(SlowFilterMode::X2)
+        .fast_filter_threshold(FastFilterThreshold::SlowFilterOnly)
+        .watchdog_state(WatchdogState::On)
+        .build();
     let mut as5600 = As5600::new(i2c);
-    assert_eq!(expected_config, as5600.config().unwrap());
+    let config = as5600.config().unwrap();
+    assert_eq!(expected_config, config);
+    assert_eq!(Some(0b1100_0000_0000_0000), config.blank_fields());
     as5600.release().done();
 }
 
@@ -125,7 +141,7 @@ fn reads_raw_angle_register() {
         vec![0x0c],
         vec![0b1110_0001, 0b0010_0011],
     )]);
-    let expected_angle = 0x0123;
+    let expected_angle = Angle::from_counts(0x0123);
     let mut as5600 = As5600::new(i2c);
     assert_eq!(expected_angle, as5600.raw_angle().unwrap());
     as5600.release().done();
@@ -138,7 +154,7 @@ fn reads_angle_register() {
         vec![0x0e],
         vec![0b1110_1000, 0b0100_0010],
     )]);
-    let expected_angle = 0x0842;
+    let expected_angle = Angle::from_counts(0x0842);
     let mut as5600 = As5600::new(i2c);
     assert_eq!(expected_angle, as5600.angle().unwrap());
     as5600.release().done();
@@ -166,3 +182,175 @@ fn reads_magnitude_register() {
     assert_eq!(expected_magnitude, as5600.magnitude().unwrap());
     as5600.release().done();
 }
+
+#[test]
+fn reads_status_flags() {
+    let i2c = Mock::new(&[
+        Transaction::write_read(0x36, vec![0x0b], vec![0x38]),
+        Transaction::write_read(0x36, vec![0x0b], vec![0x20]),
+    ]);
+    let mut as5600 = As5600::new(i2c);
+    let flags = as5600.magnet_status_flags().unwrap();
+    assert_eq!(0x38, flags.bits());
+    assert!(flags.magnet_detected() && flags.too_weak() && flags.too_strong());
+    assert_eq!(
+        StatusFlags::MAGNET_DETECTED,
+        as5600.magnet_status_flags().unwrap()
+    );
+    as5600.release().done();
+}
+
+#[test]
+fn reads_magnet_quality() {
+    let i2c = Mock::new(&[
+        Transaction::write_read(0x36, vec![0x0b], vec![0x20, 0x00, 0x00, 0x00, 0x00]),
+        Transaction::write_read(0x36, vec![0x1a], vec![0x40, 0x05, 0xDC]),
+    ]);
+    let mut as5600 = As5600::new(i2c);
+    let quality = as5600.magnet_quality(SupplyVoltage::V3_3).unwrap();
+    assert_eq!(1500, quality.magnitude);
+    assert_eq!(Placement::Good, quality.placement());
+    assert_eq!(100, quality.score());
+    as5600.release().done();
+}
+
+#[test]
+fn reloads_otp() {
+    let i2c = Mock::new(&[
+        Transaction::write(0x36, vec![0xFF, 0x01]),
+        Transaction::write(0x36, vec![0xFF, 0x11]),
+        Transaction::write(0x36, vec![0xFF, 0x10]),
+        Transaction::write_read(0x36, vec![0x01], vec![0x00, 0x64]),
+        Transaction::write_read(0x36, vec![0x03], vec![0x04, 0x4C]),
+        Transaction::write_read(0x36, vec![0x05], vec![0x00, 0x00]),
+        Transaction::write_read(0x36, vec![0x07], vec![0x00, 0x00]),
+    ]);
+    let expected = OtpSnapshot {
+        zero_position: Angle::from_counts(100),
+        maximum_position: Angle::from_counts(1100),
+        maximum_angle: Angle::ZERO,
+        config: Configuration::try_from(0).unwrap(),
+    };
+    let mut as5600 = As5600::new(i2c);
+    assert_eq!(expected, as5600.reload_otp().unwrap());
+    as5600.release().done();
+}
+
+#[test]
+fn reads_all_registers() {
+    let i2c = Mock::new(&[
+        Transaction::write_read(
+            0x36,
+            vec![0x00],
+            vec![0x01, 0x00, 0x64, 0x04, 0x4C, 0x00, 0x00, 0x00, 0x00],
+        ),
+        Transaction::write_read(0x36, vec![0x0b], vec![0x20, 0x02, 0x00, 0x01, 0xFF]),
+        Transaction::write_read(0x36, vec![0x1a], vec![0x80, 0xF8, 0x00]),
+    ]);
+    let expected = RegisterSnapshot {
+        zmco: 1,
+        zero_position: Angle::from_counts(100),
+        maximum_position: Angle::from_counts(1100),
+        maximum_angle: Angle::ZERO,
+        config: 0,
+        status: StatusFlags::MAGNET_DETECTED,
+        raw_angle: Angle::from_counts(0x200),
+        angle: Angle::from_counts(0x1FF),
+        automatic_gain_control: 0x80,
+        magnitude: 0x800,
+    };
+    let mut as5600 = As5600::new(i2c);
+    assert_eq!(expected, as5600.read_all().unwrap());
+    as5600.release().done();
+}
+
+#[test]
+fn reads_telemetry_in_two_transactions() {
+    let i2c = Mock::new(&[
+        Transaction::write_read(0x36, vec![0x0b], vec![0x28, 0x0A, 0xBC, 0x0A, 0xBB]),
+        Transaction::write_read(0x36, vec![0x1a], vec![0x10, 0x0F, 0xFF]),
+    ]);
+    let expected = Telemetry {
+        status: StatusFlags::MAGNET_DETECTED | StatusFlags::MAGNET_HIGH,
+        raw_angle: Angle::from_counts(0xABC),
+        angle: Angle::from_counts(0xABB),
+        automatic_gain_control: 0x10,
+        magnitude: 0xFFF,
+    };
+    let mut as5600 = As5600::new(i2c);
+    assert_eq!(expected, as5600.read_telemetry().unwrap());
+    as5600.release().done();
+}
+
+#[test]
+fn rejects_burst_past_pointer_holding_registers() {
+    let i2c = Mock::new(&[Transaction::write_read(0x36, vec![0x0c], vec![0x01, 0x23])]);
+    let mut as5600 = As5600::new(i2c);
+    let mut buffer = [0u8; 2];
+    as5600
+        .read_registers(Register::RawAngle, &mut buffer)
+        .unwrap();
+    assert_eq!([0x01, 0x23], buffer);
+    for register in [Register::RawAngle, Register::Angle, Register::Magnitude] {
+        assert_eq!(
+            Err(error::Error::InvalidBurstRead(register)),
+            as5600.read_registers(register, &mut [0u8; 3])
+        );
+    }
+    as5600.release().done();
+}
+
+#[test]
+fn streams_angle_after_priming() {
+    let i2c = Mock::new(&[
+        Transaction::write_read(0x36, vec![0x0e], vec![0x01, 0x00]),
+        Transaction::read(0x36, vec![0x01, 0x01]),
+        Transaction::read(0x36, vec![0x01, 0x02]),
+        Transaction::write_read(0x36, vec![0x0b], vec![0x20]),
+        Transaction::write_read(0x36, vec![0x0e], vec![0x01, 0x03]),
+        Transaction::read(0x36, vec![0x01, 0x04]),
+        Transaction::write_read(0x36, vec![0x0c], vec![0x01, 0x05]),
+    ]);
+    let mut as5600 = As5600::new(i2c);
+    as5600.set_streaming(true);
+    for counts in 0x100..=0x102 {
+        assert_eq!(Angle::from_counts(counts), as5600.angle().unwrap());
+    }
+    // Any other access moves the pointer, so the next read re-primes it.
+    as5600.magnet_status_flags().unwrap();
+    for counts in 0x103..=0x104 {
+        assert_eq!(Angle::from_counts(counts), as5600.angle().unwrap());
+    }
+    assert_eq!(Angle::from_counts(0x105), as5600.raw_angle().unwrap());
+    as5600.release().done();
+}
+
+#[test]
+fn does_not_stream_by_default() {
+    let i2c = Mock::new(&[
+        Transaction::write_read(0x36, vec![0x0e], vec![0x01, 0x00]),
+        Transaction::write_read(0x36, vec![0x0e], vec![0x01, 0x01]),
+    ]);
+    let mut as5600 = As5600::new(i2c);
+    assert!(!as5600.streaming());
+    as5600.angle().unwrap();
+    as5600.angle().unwrap();
+    as5600.release().done();
+}
+
+#[test]
+fn reads_invalid_config_leniently() {
+    let i2c = Mock::new(&[
+        Transaction::write_read(0x36, vec![0x07], vec![0x00, 0b0011_0000]),
+        Transaction::write_read(0x36, vec![0x07], vec![0x00, 0b0011_0000]),
+    ]);
+    let mut as5600 = As5600::new(i2c);
+    assert!(matches!(
+        as5600.config(),
+        Err(error::Error::Configuration(_))
+    ));
+    let raw = as5600.raw_config().unwrap();
+    assert!(raw.output_stage.is_err());
+    assert_eq!(Ok(PowerMode::Nom), raw.power_mode);
+    as5600.release().done();
+}
@@ -1,7 +1,9 @@
 use crate::{
+    angle::Angle,
+    burn::{BurnContents, BurnOutcome, BurnPolicy},
     configuration::{
         Configuration, FastFilterThreshold, Hysteresis, OutputStage, PowerMode, PwmFreq,
-        SlowFilterMode, WatchdogState,
+        RawConfiguration, SlowFilterMode, WatchdogState,
     },
     error::Error,
     As5600,
@@ -21,7 +23,7 @@ fn set_zero_position() {
     ]);
     let mut as5600 = As5600::new(i2c);
     for angle in [0x1AAF, 0x0110, 0x0ACA, 0x010B] {
-        as5600.set_zero_position(angle).unwrap();
+        as5600.set_zero_position(Angle::from_counts(angle)).unwrap();
     }
     as5600.release().done();
 }
@@ -36,7 +38,9 @@ fn set_maximum_position() {
     ]);
     let mut as5600 = As5600::new(i2c);
     for angle in [0xAFAF, 0x2010, 0x1FAF, 0x1100] {
-        as5600.set_maximum_position(angle).unwrap();
+        as5600
+            .set_maximum_position(Angle::from_counts(angle))
+            .unwrap();
     }
     as5600.release().done();
 }
@@ -51,22 +55,22 @@ fn set_maximum_angle() {
     ]);
     let mut as5600 = As5600::new(i2c);
     for angle in [0x0FFA, 0x0001, 0xAFFA, 0x1000] {
-        as5600.set_maximum_angle(angle).unwrap();
+        as5600.set_maximum_angle(Angle::from_counts(angle)).unwrap();
     }
     as5600.release().done();
 }
 
 #[test]
 fn set_config() {
-    let config = Configuration {
-        power_mode: PowerMode::Lpm1,
-        hysteresis: Hysteresis::Lsb2,
-        output_stage: OutputStage::DigitalPwm,
-        pwm_frequency: PwmFreq::PwmF2,
-        slow_filter: SlowFilterMode::X2,
-        fast_filter_threshold: FastFilterThreshold::Lsb21,
-        watchdog_state: WatchdogState::On,
-    };
+    let config = Configuration::builder()
+        .power_mode(PowerMode::Lpm1)
+        .hysteresis(Hysteresis::Lsb2)
+        .output_stage(OutputStage::DigitalPwm)
+        .pwm_frequency(PwmFreq::PwmF2)
+        .slow_filter(SlowFilterMode::X2)
+        .fast_filter_threshold(FastFilterThreshold::Lsb21)
+        .watchdog_state(WatchdogState::On)
+        .build();
     let config_bytes: [u8; 2] = u16::from(config).to_be_bytes();
     let top_most_set = config_bytes[0] | 0b1000_0000;
 
@@ -146,3 +150,158 @@ fn burn_settings_fails_when_zmco_is_not_zero() {
     );
     as5600.release().done();
 }
+
+#[test]
+fn burn_angle_dry_run_skips_burn_write() {
+    let i2c = Mock::new(&[
+        Transaction::write_read(0x36, vec![0x00], vec![0b0000_0001]),
+        Transaction::write_read(0x36, vec![0x0b], vec![0x20]),
+        Transaction::write_read(0x36, vec![0x01], vec![0x00, 0x64]),
+        Transaction::write_read(0x36, vec![0x03], vec![0x04, 0x4C]),
+    ]);
+    let mut delay = NoopDelay;
+    let mut as5600 = As5600::new(i2c);
+    as5600.set_burn_policy(BurnPolicy::DryRun);
+    assert_eq!(
+        as5600.persist_position_settings(&mut delay).unwrap(),
+        BurnOutcome::DryRun {
+            command: 0x80,
+            intended: BurnContents::Angle {
+                zero_position: Angle::from_counts(100),
+                maximum_position: Angle::from_counts(1100),
+            }
+        }
+    );
+    as5600.release().done();
+}
+
+#[test]
+fn burn_settings_dry_run_accepts_what_a_burn_accepts() {
+    let i2c = Mock::new(&[
+        Transaction::write_read(0x36, vec![0x00], vec![0b0000_0000]),
+        Transaction::write_read(0x36, vec![0x05], vec![0x04, 0x00]),
+        Transaction::write_read(0x36, vec![0x07], vec![0x00, 0b0011_0000]),
+    ]);
+    let mut delay = NoopDelay;
+    let mut as5600 = As5600::new(i2c);
+    as5600.set_burn_policy(BurnPolicy::DryRun);
+    assert_eq!(
+        as5600
+            .persist_maximum_angle_and_config_settings(&mut delay)
+            .unwrap(),
+        BurnOutcome::DryRun {
+            command: 0x40,
+            intended: BurnContents::Setting {
+                maximum_angle: Angle::from_counts(1024),
+                config: RawConfiguration::from(0b0011_0000),
+            }
+        }
+    );
+    as5600.release().done();
+}
+
+#[test]
+fn burn_settings_dry_run_still_checks_zmco() {
+    let i2c = Mock::new(&[Transaction::write_read(0x36, vec![0x00], vec![0b0000_0001])]);
+    let mut delay = NoopDelay;
+    let mut as5600 = As5600::new(i2c);
+    as5600.set_burn_policy(BurnPolicy::DryRun);
+    assert_eq!(
+        as5600
+            .persist_maximum_angle_and_config_settings(&mut delay)
+            .unwrap_err(),
+        Error::MangConfigPersistenceExhausted
+    );
+    as5600.release().done();
+}
+
+#[test]
+fn burn_denied_by_policy() {
+    let i2c = Mock::new(&[Transaction::write_read(0x36, vec![0x00], vec![0b0000_0000])]);
+    let mut delay = NoopDelay;
+    let mut as5600 = As5600::new(i2c);
+    as5600.set_burn_policy(BurnPolicy::Deny);
+    assert_eq!(
+        as5600
+            .persist_maximum_angle_and_config_settings(&mut delay)
+            .unwrap_err(),
+        Error::BurnDenied
+    );
+    as5600.release().done();
+}
+
+#[cfg(feature = "as5600l")]
+#[test]
+fn persist_address_dry_run_skips_burn_write() {
+    let i2c = Mock::new(&[Transaction::write_read(0x40, vec![0x20], vec![0x50])]);
+    let mut as5600 = As5600::with_address(0x40, i2c);
+    as5600.set_burn_policy(BurnPolicy::DryRun);
+    assert_eq!(
+        as5600.persist_address().unwrap(),
+        BurnOutcome::DryRun {
+            command: 0x40,
+            intended: BurnContents::Address { address: 0x28 }
+        }
+    );
+    as5600.release().done();
+}
+
+#[test]
+fn set_hysteresis_modifies_config() {
+    let i2c = Mock::new(&[
+        Transaction::write_read(0x36, vec![0x07], vec![0b1110_0011, 0b1010_1100]),
+        Transaction::write(0x36, vec![0x07, 0b1110_0011, 0b1010_0100]),
+    ]);
+    let mut as5600 = As5600::new(i2c);
+    let config = as5600.set_hysteresis(Hysteresis::Lsb1).unwrap();
+    assert_eq!(Hysteresis::Lsb1, config.hysteresis);
+    assert_eq!(SlowFilterMode::X2, config.slow_filter);
+    as5600.release().done();
+}
+
+#[test]
+fn modify_config_changes_several_fields_at_once() {
+    let i2c = Mock::new(&[
+        Transaction::write_read(0x36, vec![0x07], vec![0b1100_0000, 0b0000_0000]),
+        Transaction::write(0x36, vec![0x07, 0b1100_0001, 0b0000_0111]),
+    ]);
+    let mut as5600 = As5600::new(i2c);
+    let config = as5600
+        .modify_config(|config| {
+            config.power_mode = PowerMode::Lpm3;
+            config.hysteresis = Hysteresis::Lsb1;
+            config.slow_filter = SlowFilterMode::X8;
+        })
+        .unwrap();
+    assert_eq!(PowerMode::Lpm3, config.power_mode);
+    as5600.release().done();
+}
+
+#[test]
+fn set_power_mode_rejects_invalid_config() {
+    let i2c = Mock::new(&[Transaction::write_read(
+        0x36,
+        vec![0x07],
+        vec![0x00, 0b0011_0000],
+    )]);
+    let mut as5600 = As5600::new(i2c);
+    assert!(matches!(
+        as5600.set_power_mode(PowerMode::Lpm3),
+        Err(Error::Configuration(_))
+    ));
+    as5600.release().done();
+}
+
+#[test]
+fn set_config_writes_blank_fields_read_before() {
+    let i2c = Mock::new(&[
+        Transaction::write_read(0x36, vec![0x07], vec![0b1100_0000, 0b0000_0100]),
+        Transaction::write(0x36, vec![0x07, 0b1100_0000, 0b0000_1000]),
+    ]);
+    let mut as5600 = As5600::new(i2c);
+    let mut config = as5600.config().unwrap();
+    assert_eq!(Some(0b1100_0000_0000_0000), config.blank_fields());
+    config.hysteresis = Hysteresis::Lsb2;
+    as5600.set_config(config).unwrap();
+    as5600.release().done();
+}
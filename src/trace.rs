@@ -0,0 +1,666 @@
+use core::fmt;
+use core::time::Duration;
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c as BlockingI2c, NoAcknowledgeSource, Operation};
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+use crate::angle::Angle;
+#[cfg(feature = "async")]
+use crate::as5600::As5600Async;
+use crate::constants::DEFAULT_I2C_ADDRESS;
+use crate::register::pointer::AddressPointer;
+use crate::snapshot::Telemetry;
+use crate::status::StatusFlags;
+use crate::As5600;
+
+/// Magic bytes at the start of a binary trace.
+pub const BINARY_MAGIC: [u8; 4] = *b"AS56";
+
+/// Current binary trace format version, following [`BINARY_MAGIC`].
+pub const BINARY_VERSION: u8 = 1;
+
+/// First line of a CSV trace.
+pub const CSV_HEADER: &str = "timestamp_us,angle,raw_angle,status,agc,magnitude";
+
+/// Trace writing/parsing error.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// The underlying writer failed.
+    Write,
+    /// The binary trace does not start with [`BINARY_MAGIC`].
+    InvalidHeader,
+    /// The binary trace format version is not supported (version given).
+    UnsupportedVersion(u8),
+    /// The binary trace ends in the middle of a sample.
+    Truncated,
+    /// Malformed CSV line (line number given, starting at 1).
+    InvalidCsv(usize),
+}
+
+/// Timestamped output registers of one sample.
+///
+/// Encoded in [`Sample::ENCODED_LEN`] bytes, big-endian: the timestamp in microseconds as `u64`,
+/// then `ANGLE`, `RAW ANGLE`, `STATUS`, `AGC` and `MAGNITUDE`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sample {
+    /// Time of the sample, relative to an arbitrary start.
+    pub timestamp: Duration,
+    /// Output registers.
+    pub telemetry: Telemetry,
+}
+
+impl Sample {
+    /// Length of an encoded sample in bytes.
+    pub const ENCODED_LEN: usize = 16;
+
+    /// Encode the sample; the timestamp is truncated to microseconds.
+    pub fn to_bytes(&self) -> [u8; Self::ENCODED_LEN] {
+        let telemetry = &self.telemetry;
+        let mut bytes = [0; Self::ENCODED_LEN];
+        bytes[..8].copy_from_slice(&self.timestamp_us().to_be_bytes());
+        bytes[8..10].copy_from_slice(&telemetry.angle.counts().to_be_bytes());
+        bytes[10..12].copy_from_slice(&telemetry.raw_angle.counts().to_be_bytes());
+        bytes[12] = telemetry.status.bits();
+        bytes[13] = telemetry.automatic_gain_control;
+        bytes[14..].copy_from_slice(&telemetry.magnitude.to_be_bytes());
+        bytes
+    }
+
+    /// Decode a sample encoded by [`Sample::to_bytes`].
+    pub fn from_bytes(bytes: [u8; Self::ENCODED_LEN]) -> Self {
+        let word = |index: usize| u16::from_be_bytes([bytes[index], bytes[index + 1]]);
+        let mut timestamp = [0; 8];
+        timestamp.copy_from_slice(&bytes[..8]);
+        Self {
+            timestamp: Duration::from_micros(u64::from_be_bytes(timestamp)),
+            telemetry: Telemetry {
+                status: StatusFlags::from(bytes[12]),
+                raw_angle: Angle::from_counts(word(10)),
+                angle: Angle::from_counts(word(8)),
+                automatic_gain_control: bytes[13],
+                magnitude: word(14) & 0x0FFF,
+            },
+        }
+    }
+
+    /// Write the sample as a CSV line, see [`CSV_HEADER`].
+    pub fn write_csv<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        let telemetry = &self.telemetry;
+        writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            self.timestamp_us(),
+            telemetry.angle.counts(),
+            telemetry.raw_angle.counts(),
+            telemetry.status.bits(),
+            telemetry.automatic_gain_control,
+            telemetry.magnitude
+        )
+    }
+
+    /// Parse a CSV line written by [`Sample::write_csv`].
+    fn parse_csv(line: &str) -> Option<Self> {
+        let mut fields = line.trim().split(',');
+        let mut next = || fields.next().map(str::trim);
+        let timestamp = next()?.parse().ok()?;
+        let angle = next()?.parse().ok()?;
+        let raw_angle = next()?.parse().ok()?;
+        let status = next()?.parse().ok()?;
+        let automatic_gain_control = next()?.parse().ok()?;
+        let magnitude = next()?.parse().ok()?;
+        if next().is_some()
+            || angle >= Angle::COUNTS_PER_TURN
+            || raw_angle >= Angle::COUNTS_PER_TURN
+            || magnitude > 0x0FFF
+        {
+            return None;
+        }
+        Some(Self {
+            timestamp: Duration::from_micros(timestamp),
+            telemetry: Telemetry {
+                status: StatusFlags::from_bits_retain(status),
+                raw_angle: Angle::from_counts(raw_angle),
+                angle: Angle::from_counts(angle),
+                automatic_gain_control,
+                magnitude,
+            },
+        })
+    }
+
+    fn timestamp_us(&self) -> u64 {
+        u64::try_from(self.timestamp.as_micros()).unwrap_or(u64::MAX)
+    }
+}
+
+/// Destination for recorded samples.
+pub trait TraceWriter {
+    /// Write one sample.
+    fn write(&mut self, sample: &Sample) -> Result<(), Error>;
+}
+
+/// Writes samples as CSV text, starting with [`CSV_HEADER`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CsvWriter<W> {
+    out: W,
+    header_written: bool,
+}
+
+impl<W: fmt::Write> CsvWriter<W> {
+    /// Create a CSV writer. The header is written along with the first sample.
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            header_written: false,
+        }
+    }
+
+    /// Release the underlying writer.
+    pub fn release(self) -> W {
+        self.out
+    }
+}
+
+impl<W: fmt::Write> TraceWriter for CsvWriter<W> {
+    fn write(&mut self, sample: &Sample) -> Result<(), Error> {
+        if !self.header_written {
+            writeln!(self.out, "{CSV_HEADER}").map_err(|_| Error::Write)?;
+            self.header_written = true;
+        }
+        sample.write_csv(&mut self.out).map_err(|_| Error::Write)
+    }
+}
+
+/// Writes samples in the binary format: [`BINARY_MAGIC`], [`BINARY_VERSION`], then
+/// [`Sample::ENCODED_LEN`] bytes per sample.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BinaryWriter<W> {
+    out: W,
+    header_written: bool,
+}
+
+impl<W: embedded_io::Write> BinaryWriter<W> {
+    /// Create a binary writer. The header is written along with the first sample.
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            header_written: false,
+        }
+    }
+
+    /// Release the underlying writer.
+    pub fn release(self) -> W {
+        self.out
+    }
+}
+
+impl<W: embedded_io::Write> TraceWriter for BinaryWriter<W> {
+    fn write(&mut self, sample: &Sample) -> Result<(), Error> {
+        if !self.header_written {
+            self.out
+                .write_all(&BINARY_MAGIC)
+                .and_then(|()| self.out.write_all(&[BINARY_VERSION]))
+                .map_err(|_| Error::Write)?;
+            self.header_written = true;
+        }
+        self.out
+            .write_all(&sample.to_bytes())
+            .map_err(|_| Error::Write)
+    }
+}
+
+/// Parse a CSV trace. The header and empty lines are skipped.
+pub fn read_csv(text: &str) -> impl Iterator<Item = Result<Sample, Error>> + '_ {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && line.trim() != CSV_HEADER)
+        .map(|(index, line)| Sample::parse_csv(line).ok_or(Error::InvalidCsv(index + 1)))
+}
+
+/// Parse a binary trace.
+pub fn read_binary(bytes: &[u8]) -> Result<impl Iterator<Item = Sample> + '_, Error> {
+    let samples = bytes
+        .strip_prefix(&BINARY_MAGIC)
+        .ok_or(Error::InvalidHeader)?;
+    let (&version, samples) = samples.split_first().ok_or(Error::InvalidHeader)?;
+    if version != BINARY_VERSION {
+        return Err(Error::UnsupportedVersion(version));
+    }
+    if samples.len() % Sample::ENCODED_LEN != 0 {
+        return Err(Error::Truncated);
+    }
+    Ok(samples.chunks_exact(Sample::ENCODED_LEN).map(|chunk| {
+        let mut bytes = [0; Sample::ENCODED_LEN];
+        bytes.copy_from_slice(chunk);
+        Sample::from_bytes(bytes)
+    }))
+}
+
+/// Trace recorder wrapping a driver instance.
+///
+/// Works with the blocking [`As5600`] and, with the `async` feature, `asynch::As5600`.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Recorder<D, W> {
+    driver: D,
+    writer: W,
+}
+
+impl<D, W> Recorder<D, W> {
+    /// Create a recorder writing to `writer`.
+    pub fn new(driver: D, writer: W) -> Self {
+        Self { driver, writer }
+    }
+
+    /// Get the wrapped driver, e.g. to read other registers in between.
+    pub fn driver(&mut self) -> &mut D {
+        &mut self.driver
+    }
+
+    /// Get the writer.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Release driver and writer, consuming the recorder.
+    pub fn release(self) -> (D, W) {
+        (self.driver, self.writer)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(As5600(sync), AsyncI2c(sync = "BlockingI2c", async)),
+    keep_self,
+    sync(),
+    async(feature = "async")
+)]
+impl<I, E, W> Recorder<As5600<I>, W>
+where
+    I: AsyncI2c<Error = E>,
+    W: TraceWriter,
+{
+    /// Read the output registers and write them as a sample taken at `timestamp`.
+    pub async fn record(&mut self, timestamp: Duration) -> Result<Sample, RecordError<E>> {
+        let telemetry = self
+            .driver
+            .read_telemetry()
+            .await
+            .map_err(RecordError::Driver)?;
+        let sample = Sample {
+            timestamp,
+            telemetry,
+        };
+        self.writer.write(&sample).map_err(RecordError::Trace)?;
+        Ok(sample)
+    }
+}
+
+/// Error of [`Recorder::record`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RecordError<E> {
+    /// Reading the output registers failed.
+    Driver(crate::error::Error<E>),
+    /// Writing the sample failed.
+    Trace(Error),
+}
+
+/// Bus error of a [`Replay`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ReplayError {
+    /// No device answered at the given address.
+    NoAcknowledge(u8),
+    /// All samples have been replayed.
+    EndOfTrace,
+}
+
+impl embedded_hal::i2c::Error for ReplayError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::NoAcknowledge(_) => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+            Self::EndOfTrace => ErrorKind::Other,
+        }
+    }
+}
+
+/// Replays recorded samples through the `I2c` traits, so application code can be rerun
+/// deterministically against a trace.
+///
+/// The output registers (`STATUS` through `MAGNITUDE`) return the values of the current sample.
+/// A read which repeats a register byte already read from the current sample first advances to
+/// the next sample. So every
+/// [`As5600::angle`] call, and every [`As5600::read_telemetry`] call as done by the
+/// [`Recorder`], consumes one sample. After the last sample, reads fail with
+/// [`ReplayError::EndOfTrace`].
+///
+/// The settings registers (`ZPOS` through `CONF`) can be written and read back, but do not
+/// affect the replayed `ANGLE`. Burn commands are ignored.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Replay<'a> {
+    address: u8,
+    samples: &'a [Sample],
+    index: usize,
+    /// Bit mask of the output register bytes read from the current sample.
+    read: u32,
+    pointer: AddressPointer,
+    /// Registers `ZMCO` through `CONF`.
+    settings: [u8; 9],
+}
+
+impl<'a> Replay<'a> {
+    /// First output register.
+    const OUTPUT_START: u8 = 0x0B;
+    /// Last output register.
+    const OUTPUT_END: u8 = 0x1C;
+
+    /// Replay `samples` at the default I2C address.
+    pub fn new(samples: &'a [Sample]) -> Self {
+        Self::with_address(DEFAULT_I2C_ADDRESS, samples)
+    }
+
+    /// Replay `samples` at the given I2C address.
+    pub fn with_address(address: u8, samples: &'a [Sample]) -> Self {
+        Self {
+            address,
+            samples,
+            index: 0,
+            read: 0,
+            pointer: AddressPointer::new(),
+            settings: [0; 9],
+        }
+    }
+
+    /// Get the index of the sample currently served.
+    pub fn position(&self) -> usize {
+        self.index
+    }
+
+    /// Get the sample currently served, if any are left.
+    pub fn sample(&self) -> Option<&Sample> {
+        self.samples.get(self.index)
+    }
+
+    /// Bit of an output register byte in the mask of read bytes.
+    fn output_bit(address: u8) -> Option<u32> {
+        (Self::OUTPUT_START..=Self::OUTPUT_END)
+            .contains(&address)
+            .then(|| 1 << (address - Self::OUTPUT_START))
+    }
+
+    /// Advance to the next sample if a read of `len` bytes repeats an output register byte.
+    fn start_read(&mut self, len: usize) -> Result<(), ReplayError> {
+        let mut pointer = self.pointer;
+        let mut mask = 0;
+        for _ in 0..len {
+            mask |= Self::output_bit(pointer.address()).unwrap_or(0);
+            pointer.advance_read();
+        }
+        if self.read & mask != 0 {
+            self.index = self.index.saturating_add(1);
+            self.read = 0;
+        }
+        self.read |= mask;
+        if mask != 0 && self.sample().is_none() {
+            return Err(ReplayError::EndOfTrace);
+        }
+        Ok(())
+    }
+
+    fn read_register(&self, address: u8) -> u8 {
+        let Some(telemetry) = self
+            .sample()
+            .filter(|_| Self::output_bit(address).is_some())
+            .map(|sample| sample.telemetry)
+        else {
+            return self
+                .settings
+                .get(usize::from(address))
+                .copied()
+                .unwrap_or(0);
+        };
+        let [raw_h, raw_l] = telemetry.raw_angle.counts().to_be_bytes();
+        let [angle_h, angle_l] = telemetry.angle.counts().to_be_bytes();
+        let [magnitude_h, magnitude_l] = telemetry.magnitude.to_be_bytes();
+        match address {
+            0x0B => telemetry.status.bits(),
+            0x0C => raw_h,
+            0x0D => raw_l,
+            0x0E => angle_h,
+            0x0F => angle_l,
+            0x1A => telemetry.automatic_gain_control,
+            0x1B => magnitude_h,
+            0x1C => magnitude_l,
+            _ => 0,
+        }
+    }
+
+    fn write_register(&mut self, address: u8, value: u8) {
+        // `ZMCO` is read-only, all other registers ignore writes.
+        if (0x01..=0x08).contains(&address) {
+            self.settings[usize::from(address)] = value;
+        }
+    }
+
+    fn execute(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), ReplayError> {
+        if address != self.address {
+            return Err(ReplayError::NoAcknowledge(address));
+        }
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    let Some((&pointer, data)) = bytes.split_first() else {
+                        continue;
+                    };
+                    self.pointer.set(pointer);
+                    for &byte in data {
+                        self.write_register(self.pointer.address(), byte);
+                        self.pointer.advance_write();
+                    }
+                }
+                Operation::Read(buffer) => {
+                    self.start_read(buffer.len())?;
+                    for byte in buffer.iter_mut() {
+                        *byte = self.read_register(self.pointer.address());
+                        self.pointer.advance_read();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ErrorType for Replay<'_> {
+    type Error = ReplayError;
+}
+
+impl BlockingI2c for Replay<'_> {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.execute(address, operations)
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncI2c for Replay<'_> {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.execute(address, operations)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use core::time::Duration;
+
+    use proptest::prelude::*;
+
+    use crate::angle::Angle;
+    use crate::error;
+    use crate::sim::SimulatedAs5600;
+    use crate::snapshot::Telemetry;
+    use crate::status::StatusFlags;
+    use crate::trace::{
+        read_binary, read_csv, BinaryWriter, CsvWriter, Error, Recorder, Replay, ReplayError,
+        Sample, TraceWriter, CSV_HEADER,
+    };
+    use crate::As5600;
+
+    fn sample(micros: u64, raw_angle: u16, angle: u16) -> Sample {
+        Sample {
+            timestamp: Duration::from_micros(micros),
+            telemetry: Telemetry {
+                status: StatusFlags::from(0x20),
+                raw_angle: Angle::from_counts(raw_angle),
+                angle: Angle::from_counts(angle),
+                automatic_gain_control: 128,
+                magnitude: 2048,
+            },
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn encodings_roundtrip(
+            micros in 0u64..u64::MAX,
+            raw_angle in 0u16..4096,
+            angle in 0u16..4096,
+            status: u8,
+            agc: u8,
+            magnitude in 0u16..4096,
+        ) {
+            let sample = Sample {
+                timestamp: Duration::from_micros(micros),
+                telemetry: Telemetry {
+                    status: StatusFlags::from(status),
+                    raw_angle: Angle::from_counts(raw_angle),
+                    angle: Angle::from_counts(angle),
+                    automatic_gain_control: agc,
+                    magnitude,
+                },
+            };
+            prop_assert_eq!(sample, Sample::from_bytes(sample.to_bytes()));
+            let mut line = String::new();
+            sample.write_csv(&mut line).unwrap();
+            prop_assert_eq!(Some(Ok(sample)), read_csv(&line).next());
+        }
+    }
+
+    #[test]
+    fn records_csv_and_binary() {
+        let mut sim = SimulatedAs5600::new();
+        sim.set_raw_angle(Angle::from_counts(110));
+        let mut recorder = Recorder::new(As5600::new(sim), CsvWriter::new(String::new()));
+        let mut samples = Vec::new();
+        for step in 0..3 {
+            let timestamp = Duration::from_millis(10 * step);
+            samples.push(recorder.record(timestamp).unwrap());
+        }
+        let (_, writer) = recorder.release();
+        let csv = writer.release();
+        assert_eq!(Some(CSV_HEADER), csv.lines().next());
+        assert_eq!(Some("10000,110,110,32,128,2048"), csv.lines().nth(2));
+        let parsed: Result<Vec<_>, _> = read_csv(&csv).collect();
+        assert_eq!(Ok(samples.clone()), parsed);
+
+        let mut writer = BinaryWriter::new(Vec::new());
+        for sample in &samples {
+            writer.write(sample).unwrap();
+        }
+        let bytes = writer.release();
+        assert_eq!(5 + 3 * Sample::ENCODED_LEN, bytes.len());
+        assert_eq!(samples, read_binary(&bytes).unwrap().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn rejects_malformed_traces() {
+        assert_eq!(
+            Some(Err(Error::InvalidCsv(2))),
+            read_csv("timestamp_us,angle,raw_angle,status,agc,magnitude\n1,2,3\n").next()
+        );
+        assert_eq!(
+            Some(Err(Error::InvalidCsv(1))),
+            read_csv("1,4096,0,0,0,0").next()
+        );
+        assert_eq!(
+            Some(Err(Error::InvalidCsv(1))),
+            read_csv("1,0,0,0,0,4096").next()
+        );
+        assert!(matches!(read_binary(b"AS5"), Err(Error::InvalidHeader)));
+        assert!(matches!(
+            read_binary(b"AS56\x02"),
+            Err(Error::UnsupportedVersion(2))
+        ));
+        assert!(matches!(
+            read_binary(b"AS56\x01\x00"),
+            Err(Error::Truncated)
+        ));
+        assert_eq!(0, read_binary(b"AS56\x01").unwrap().count());
+    }
+
+    #[test]
+    fn replays_one_sample_per_read() {
+        let samples = [
+            sample(0, 100, 10),
+            sample(1000, 200, 20),
+            sample(2000, 300, 30),
+        ];
+        let mut as5600 = As5600::new(Replay::new(&samples));
+        assert_eq!(Angle::from_counts(10), as5600.angle().unwrap());
+        assert_eq!(Angle::from_counts(100), as5600.raw_angle().unwrap());
+        assert_eq!(Angle::from_counts(20), as5600.angle().unwrap());
+        let telemetry = as5600.read_telemetry().unwrap();
+        assert_eq!(samples[2].telemetry, telemetry);
+        assert_eq!(
+            Err(error::Error::Communication(ReplayError::EndOfTrace)),
+            as5600.angle()
+        );
+    }
+
+    #[test]
+    fn replays_recorded_telemetry_reads() {
+        let samples = [sample(0, 100, 10), sample(1000, 200, 20)];
+        let mut recorder = Recorder::new(
+            As5600::new(Replay::new(&samples)),
+            CsvWriter::new(String::new()),
+        );
+        for expected in &samples {
+            assert_eq!(*expected, recorder.record(expected.timestamp).unwrap());
+        }
+        assert!(recorder.record(Duration::ZERO).is_err());
+    }
+
+    #[test]
+    fn replay_keeps_settings() {
+        let samples = [sample(0, 100, 10)];
+        let mut as5600 = As5600::new(Replay::new(&samples));
+        as5600.set_zero_position(Angle::from_counts(100)).unwrap();
+        assert_eq!(Angle::from_counts(100), as5600.zero_position().unwrap());
+        assert_eq!(0, as5600.zmco().unwrap());
+        let replay = as5600.release();
+        assert_eq!(0, replay.position());
+        assert_eq!(Some(&samples[0]), replay.sample());
+        assert!(matches!(
+            As5600::with_address(0x40, replay).angle(),
+            Err(error::Error::Communication(ReplayError::NoAcknowledge(
+                0x40
+            )))
+        ));
+    }
+}
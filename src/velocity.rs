@@ -0,0 +1,370 @@
+use core::f32::consts::TAU;
+use core::time::Duration;
+
+use crate::angle::Angle;
+
+/// Angular velocity and acceleration estimate.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Estimate {
+    /// Angular velocity in counts per second.
+    pub velocity: f32,
+    /// Angular acceleration in counts per second squared.
+    pub acceleration: f32,
+}
+
+impl Estimate {
+    /// Angular velocity in radians per second.
+    pub fn rad_per_sec(&self) -> f32 {
+        self.velocity * TAU / f32::from(Angle::COUNTS_PER_TURN)
+    }
+
+    /// Angular velocity in degrees per second.
+    pub fn deg_per_sec(&self) -> f32 {
+        self.velocity * 360.0 / f32::from(Angle::COUNTS_PER_TURN)
+    }
+
+    /// Angular velocity in revolutions per minute.
+    pub fn rpm(&self) -> f32 {
+        self.velocity * 60.0 / f32::from(Angle::COUNTS_PER_TURN)
+    }
+
+    /// Angular acceleration in radians per second squared.
+    pub fn rad_per_sec2(&self) -> f32 {
+        self.acceleration * TAU / f32::from(Angle::COUNTS_PER_TURN)
+    }
+}
+
+/// Velocity estimator fed by timestamped angle samples.
+///
+/// Timestamps are provided by the caller, relative to an arbitrary but fixed epoch.
+/// Samples which are not newer than the previous sample are ignored.
+/// Wrap-arounds between consecutive samples are unwrapped using the shortest-path rule,
+/// so the angle must change by less than half a turn between samples.
+pub trait Estimator {
+    /// Feed a new sample. Returns an estimate once enough samples are available.
+    fn update(&mut self, timestamp: Duration, angle: Angle) -> Option<Estimate>;
+
+    /// Forget all samples.
+    fn reset(&mut self);
+}
+
+/// Unwraps single-turn angles into a continuous position in counts.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+struct Unwrapper {
+    last: Option<(Duration, Angle)>,
+    position: i64,
+}
+
+impl Unwrapper {
+    /// Returns the elapsed time since the previous sample and the unwrapped position.
+    fn update(&mut self, timestamp: Duration, angle: Angle) -> Option<(Option<f32>, i64)> {
+        match self.last {
+            None => {
+                self.last = Some((timestamp, angle));
+                self.position = 0;
+                Some((None, 0))
+            }
+            Some((last_timestamp, _)) if timestamp <= last_timestamp => None,
+            Some((last_timestamp, last_angle)) => {
+                self.position += i64::from(angle.shortest_difference(last_angle));
+                self.last = Some((timestamp, angle));
+                let dt = (timestamp - last_timestamp).as_secs_f32();
+                Some((Some(dt), self.position))
+            }
+        }
+    }
+}
+
+/// Plain finite differencing of consecutive samples.
+///
+/// Lowest latency, but noisy at 12-bit resolution and high sample rates.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Differencing {
+    unwrapper: Unwrapper,
+    last_position: i64,
+    last_velocity: Option<f32>,
+}
+
+impl Differencing {
+    /// Create a new differencing estimator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Estimator for Differencing {
+    fn update(&mut self, timestamp: Duration, angle: Angle) -> Option<Estimate> {
+        let (dt, position) = self.unwrapper.update(timestamp, angle)?;
+        let delta = position - self.last_position;
+        self.last_position = position;
+        let dt = dt?;
+        let velocity = delta as f32 / dt;
+        let acceleration = self
+            .last_velocity
+            .map_or(0.0, |last_velocity| (velocity - last_velocity) / dt);
+        self.last_velocity = Some(velocity);
+        Some(Estimate {
+            velocity,
+            acceleration,
+        })
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Least-squares fit over a moving window of the last `N` samples.
+///
+/// With three or more samples, a quadratic is fitted, giving velocity and acceleration
+/// at the newest sample. With two samples, the estimate degrades to plain differencing.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Regression<const N: usize> {
+    unwrapper: Unwrapper,
+    /// Ring buffer of (timestamp, unwrapped position).
+    window: [(Duration, i64); N],
+    len: usize,
+    next: usize,
+}
+
+impl<const N: usize> Default for Regression<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Regression<N> {
+    /// Create a new regression estimator.
+    ///
+    /// A window shorter than 2 samples fails to compile.
+    pub fn new() -> Self {
+        const { assert!(N >= 2, "regression window needs at least 2 samples") };
+        Self {
+            unwrapper: Unwrapper::default(),
+            window: [(Duration::ZERO, 0); N],
+            len: 0,
+            next: 0,
+        }
+    }
+
+    fn fit(&self) -> Option<Estimate> {
+        let newest = self.window[(self.next + N - 1) % N];
+        // Sums of t^k and p * t^k, relative to the newest sample for numerical stability.
+        let (mut s, mut tp) = ([0.0f64; 5], [0.0f64; 3]);
+        for &(timestamp, position) in self.window.iter().take(self.len) {
+            let t = -(newest.0 - timestamp).as_secs_f64();
+            let p = (position - newest.1) as f64;
+            let mut t_k = 1.0;
+            for (k, s_k) in s.iter_mut().enumerate() {
+                if k < 3 {
+                    tp[k] += p * t_k;
+                }
+                *s_k += t_k;
+                t_k *= t;
+            }
+        }
+        if self.len >= 3 {
+            // Solve the normal equations of p = a + b t + c t^2 by Cramer's rule.
+            let det3 = |m: [[f64; 3]; 3]| {
+                m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+                    - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+                    + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+            };
+            let m = [[s[0], s[1], s[2]], [s[1], s[2], s[3]], [s[2], s[3], s[4]]];
+            let det = det3(m);
+            if det != 0.0 {
+                let mut mb = m;
+                let mut mc = m;
+                for row in 0..3 {
+                    mb[row][1] = tp[row];
+                    mc[row][2] = tp[row];
+                }
+                return Some(Estimate {
+                    velocity: (det3(mb) / det) as f32,
+                    acceleration: (2.0 * det3(mc) / det) as f32,
+                });
+            }
+        }
+        let det = s[0] * s[2] - s[1] * s[1];
+        if det == 0.0 {
+            return None;
+        }
+        Some(Estimate {
+            velocity: ((s[0] * tp[1] - s[1] * tp[0]) / det) as f32,
+            acceleration: 0.0,
+        })
+    }
+}
+
+impl<const N: usize> Estimator for Regression<N> {
+    fn update(&mut self, timestamp: Duration, angle: Angle) -> Option<Estimate> {
+        let (_, position) = self.unwrapper.update(timestamp, angle)?;
+        self.window[self.next] = (timestamp, position);
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+        if self.len < 2 {
+            return None;
+        }
+        self.fit()
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// Alpha-beta tracking observer.
+///
+/// Predicts the position from the velocity estimate and corrects both with the residual.
+/// This is equivalent to a type-2 PLL with proportional gain `alpha / dt` and integral gain
+/// `beta / dt²`. Smaller gains mean less noise but more lag.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TrackingObserver {
+    alpha: f32,
+    beta: f32,
+    unwrapper: Unwrapper,
+    position: f64,
+    velocity: f64,
+}
+
+impl TrackingObserver {
+    /// Create a new tracking observer.
+    /// For stability, `0 < alpha <= 1` and `0 < beta < 4 - 2 * alpha`.
+    pub fn new(alpha: f32, beta: f32) -> Self {
+        Self {
+            alpha,
+            beta,
+            unwrapper: Unwrapper::default(),
+            position: 0.0,
+            velocity: 0.0,
+        }
+    }
+
+    /// Create a critically damped observer from a single gain `0 < alpha < 1`.
+    pub fn critically_damped(alpha: f32) -> Self {
+        let beta = 2.0 * (2.0 - alpha) - 4.0 * sqrt(1.0 - alpha);
+        Self::new(alpha, beta)
+    }
+
+    /// Get the estimated position in counts, relative to the first sample.
+    pub fn position(&self) -> f64 {
+        self.position
+    }
+}
+
+/// Square root by Newton's method.
+///
+/// `core` has none, and `libm` is only pulled in by the `sim` feature. This runs once when
+/// creating an observer, so it does not justify a mandatory dependency.
+fn sqrt(x: f32) -> f32 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let mut y = if x > 1.0 { x } else { 1.0 };
+    for _ in 0..16 {
+        y = 0.5 * (y + x / y);
+    }
+    y
+}
+
+impl Estimator for TrackingObserver {
+    fn update(&mut self, timestamp: Duration, angle: Angle) -> Option<Estimate> {
+        let (dt, measured) = self.unwrapper.update(timestamp, angle)?;
+        let Some(dt) = dt else {
+            self.position = 0.0;
+            self.velocity = 0.0;
+            return None;
+        };
+        let dt = f64::from(dt);
+        let predicted = self.position + self.velocity * dt;
+        let residual = measured as f64 - predicted;
+        let velocity = self.velocity + f64::from(self.beta) * residual / dt;
+        let acceleration = (velocity - self.velocity) / dt;
+        self.position = predicted + f64::from(self.alpha) * residual;
+        self.velocity = velocity;
+        Some(Estimate {
+            velocity: velocity as f32,
+            acceleration: acceleration as f32,
+        })
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.alpha, self.beta);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::time::Duration;
+
+    use crate::angle::Angle;
+    use crate::velocity::{Differencing, Estimate, Estimator, Regression, TrackingObserver};
+
+    /// Samples of a constant acceleration ramp, wrapping through zero several times.
+    fn ramp(n: usize, velocity: f64, acceleration: f64) -> impl Iterator<Item = (Duration, Angle)> {
+        (0..n).map(move |i| {
+            let t = i as f64 * 0.001;
+            let position = 100.0 + velocity * t + 0.5 * acceleration * t * t;
+            let counts = position.round().rem_euclid(4096.0) as u16;
+            (
+                Duration::from_micros(i as u64 * 1000),
+                Angle::from_counts(counts),
+            )
+        })
+    }
+
+    fn last_estimate(estimator: &mut impl Estimator, n: usize, v: f64, a: f64) -> Estimate {
+        ramp(n, v, a)
+            .filter_map(|(t, angle)| estimator.update(t, angle))
+            .last()
+            .unwrap()
+    }
+
+    #[test]
+    fn estimate_units() {
+        let estimate = Estimate {
+            velocity: 4096.0,
+            acceleration: 4096.0,
+        };
+        assert_eq!(60.0, estimate.rpm());
+        assert_eq!(360.0, estimate.deg_per_sec());
+        assert_eq!(core::f32::consts::TAU, estimate.rad_per_sec());
+        assert_eq!(core::f32::consts::TAU, estimate.rad_per_sec2());
+    }
+
+    #[test]
+    fn differencing_unwraps() {
+        let mut estimator = Differencing::new();
+        assert_eq!(
+            None,
+            estimator.update(Duration::from_millis(0), Angle::from_counts(4000))
+        );
+        let estimate = estimator
+            .update(Duration::from_millis(10), Angle::from_counts(100))
+            .unwrap();
+        assert_eq!(19600.0, estimate.velocity);
+        assert_eq!(
+            None,
+            estimator.update(Duration::from_millis(10), Angle::ZERO)
+        );
+    }
+
+    #[test]
+    fn regression_recovers_constant_acceleration() {
+        let mut estimator = Regression::<16>::new();
+        let estimate = last_estimate(&mut estimator, 200, 200_000.0, 400_000.0);
+        let expected_velocity = 200_000.0 + 400_000.0 * 0.199;
+        assert!((estimate.velocity - expected_velocity).abs() < 500.0);
+        assert!((estimate.acceleration - 400_000.0).abs() < 100_000.0);
+    }
+
+    #[test]
+    fn observer_converges_to_constant_velocity() {
+        let mut estimator = TrackingObserver::critically_damped(0.3);
+        let estimate = last_estimate(&mut estimator, 500, 300_000.0, 0.0);
+        assert!((estimate.velocity - 300_000.0).abs() < 1000.0);
+        estimator.reset();
+        assert_eq!(0.0, estimator.position());
+    }
+}
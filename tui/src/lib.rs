@@ -0,0 +1,93 @@
+#![deny(unsafe_code)]
+#![deny(missing_docs)]
+
+//! Terminal dashboard for the AS5600.
+//!
+//! Shows a dial for the angle, bars for AGC and magnitude, the magnet status and the decoded
+//! configuration. The zero and maximum position can be set at the current magnet position.
+
+use std::fmt::Debug;
+
+use as5600::angle::Angle;
+use as5600::magnet::SupplyVoltage;
+use as5600::snapshot::RegisterSnapshot;
+use as5600::As5600;
+use embedded_hal::i2c::I2c;
+use ratatui::crossterm::event::KeyCode;
+
+pub mod ui;
+
+#[cfg(test)]
+mod test;
+
+/// Dashboard state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct App {
+    /// Supply voltage, which determines the AGC range.
+    pub supply: SupplyVoltage,
+    /// Registers from the last successful refresh.
+    pub snapshot: Option<RegisterSnapshot>,
+    /// Result of the last action or error.
+    pub message: String,
+    /// Whether the user asked to quit.
+    pub quit: bool,
+}
+
+impl App {
+    /// Key bindings, as shown in the help line.
+    pub const HELP: &'static str = "z: set ZPOS  m: set MPOS  c: clear ZPOS/MPOS  q: quit";
+
+    /// Create the dashboard state; nothing is read yet.
+    pub fn new(supply: SupplyVoltage) -> Self {
+        Self {
+            supply,
+            snapshot: None,
+            message: String::new(),
+            quit: false,
+        }
+    }
+
+    /// Read all registers. On error, the previous snapshot is kept.
+    pub fn refresh<I, E>(&mut self, as5600: &mut As5600<I>)
+    where
+        I: I2c<Error = E>,
+        E: Debug,
+    {
+        match as5600.read_all() {
+            Ok(snapshot) => self.snapshot = Some(snapshot),
+            Err(error) => self.message = format!("Read failed: {error:?}"),
+        }
+    }
+
+    /// Handle a key press.
+    pub fn handle_key<I, E>(&mut self, key: KeyCode, as5600: &mut As5600<I>)
+    where
+        I: I2c<Error = E>,
+        E: Debug,
+    {
+        let result = match key {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.quit = true;
+                return;
+            }
+            KeyCode::Char('z') => as5600.raw_angle().and_then(|raw_angle| {
+                as5600.set_zero_position(raw_angle)?;
+                Ok(format!("ZPOS set to {}", raw_angle.counts()))
+            }),
+            KeyCode::Char('m') => as5600.raw_angle().and_then(|raw_angle| {
+                as5600.set_maximum_position(raw_angle)?;
+                Ok(format!("MPOS set to {}", raw_angle.counts()))
+            }),
+            KeyCode::Char('c') => as5600
+                .set_zero_position(Angle::ZERO)
+                .and_then(|()| as5600.set_maximum_position(Angle::ZERO))
+                .map(|()| String::from("ZPOS and MPOS cleared")),
+            _ => return,
+        };
+        self.message = match result {
+            Ok(message) => message,
+            Err(error) => format!("Write failed: {error:?}"),
+        };
+        self.refresh(as5600);
+    }
+}
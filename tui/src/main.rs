@@ -0,0 +1,89 @@
+use std::fmt::Debug;
+use std::io;
+use std::time::Duration;
+
+use as5600::angle::Angle;
+use as5600::constants::{parse_address, DEFAULT_I2C_ADDRESS};
+use as5600::magnet::SupplyVoltage;
+use as5600::sim::{Motion, SimulatedAs5600};
+use as5600::As5600;
+use as5600_tui::{ui, App};
+use clap::Parser;
+use embedded_hal::i2c::I2c;
+use linux_embedded_hal::I2cdev;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use ratatui::DefaultTerminal;
+
+/// Live dashboard for an AS5600 on Linux I2C.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// I2C device.
+    #[arg(long, default_value = "/dev/i2c-1")]
+    device: String,
+    /// I2C address, decimal or hexadecimal with `0x` prefix.
+    #[arg(long, default_value_t = DEFAULT_I2C_ADDRESS, value_parser = parse_address)]
+    address: u8,
+    /// Use a simulated, slowly swinging device instead of the I2C device.
+    #[arg(long)]
+    sim: bool,
+    /// Supply voltage, `3v3` or `5v`, which determines the AGC range.
+    #[arg(long, default_value = "5v")]
+    supply: SupplyVoltage,
+    /// Time between two reads in milliseconds.
+    #[arg(long, default_value_t = 50)]
+    interval_ms: u64,
+}
+
+fn run<I, E>(
+    terminal: &mut DefaultTerminal,
+    as5600: &mut As5600<I>,
+    app: &mut App,
+    interval: Duration,
+) -> io::Result<()>
+where
+    I: I2c<Error = E>,
+    E: Debug,
+{
+    while !app.quit {
+        app.refresh(as5600);
+        terminal.draw(|frame| ui::draw(frame, app))?;
+        if event::poll(interval)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key.code, as5600);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    let cli = Cli::parse();
+    let mut app = App::new(cli.supply);
+    let interval = Duration::from_millis(cli.interval_ms);
+    if cli.sim {
+        let mut sim = SimulatedAs5600::with_address(cli.address);
+        // A refresh takes three bus transactions, so simulated time roughly follows real time.
+        sim.set_tick(interval / 3);
+        sim.set_motion(Motion::Sine {
+            center: Angle::from_counts(2048),
+            amplitude: 1500.0,
+            frequency: 0.1,
+        });
+        sim.set_noise(1.0, 1);
+        let mut as5600 = As5600::with_address(cli.address, sim);
+        let mut terminal = ratatui::init();
+        let result = run(&mut terminal, &mut as5600, &mut app, interval);
+        ratatui::restore();
+        result
+    } else {
+        let i2c = I2cdev::new(&cli.device).map_err(io::Error::other)?;
+        let mut as5600 = As5600::with_address(cli.address, i2c);
+        let mut terminal = ratatui::init();
+        let result = run(&mut terminal, &mut as5600, &mut app, interval);
+        ratatui::restore();
+        result
+    }
+}
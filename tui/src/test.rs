@@ -0,0 +1,79 @@
+use as5600::angle::Angle;
+use as5600::magnet::SupplyVoltage;
+use as5600::sim::SimulatedAs5600;
+use as5600::status::StatusFlags;
+use as5600::As5600;
+use ratatui::backend::TestBackend;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::Terminal;
+
+use crate::{ui, App};
+
+fn render(app: &App) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    terminal.draw(|frame| ui::draw(frame, app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    buffer
+        .content()
+        .chunks(usize::from(buffer.area.width))
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>() + "\n")
+        .collect()
+}
+
+#[test]
+fn renders_registers() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_raw_angle(Angle::from_counts(1024));
+    let mut as5600 = As5600::new(sim);
+    let mut app = App::new(SupplyVoltage::V5);
+    assert!(render(&app).contains("Waiting for the first read"));
+
+    app.refresh(&mut as5600);
+    let screen = render(&app);
+    assert!(screen.contains("Angle 90.0° (raw 90.0°)"));
+    assert!(screen.contains("ZPOS 0 MPOS 0"));
+    assert!(screen.contains("MagnetDetected"));
+    assert!(screen.contains("Hysteresis: Off"));
+    assert!(screen.contains("Configuration 0x0000"));
+    assert!(screen.contains(App::HELP));
+}
+
+#[test]
+fn renders_invalid_status() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_status(StatusFlags::from_bits_retain(0));
+    let mut as5600 = As5600::new(sim);
+    let mut app = App::new(SupplyVoltage::V3_3);
+    app.refresh(&mut as5600);
+    assert!(render(&app).contains("InvalidBitPattern(0)"));
+}
+
+#[test]
+fn sets_positions_at_current_angle() {
+    let mut sim = SimulatedAs5600::new();
+    sim.set_raw_angle(Angle::from_counts(100));
+    let mut as5600 = As5600::new(sim);
+    let mut app = App::new(SupplyVoltage::V5);
+
+    app.handle_key(KeyCode::Char('z'), &mut as5600);
+    assert_eq!("ZPOS set to 100", app.message);
+    assert_eq!(Angle::from_counts(100), app.snapshot.unwrap().zero_position);
+
+    let mut sim = as5600.release();
+    sim.set_raw_angle(Angle::from_counts(1100));
+    let mut as5600 = As5600::new(sim);
+    app.handle_key(KeyCode::Char('m'), &mut as5600);
+    assert_eq!(
+        Angle::from_counts(1100),
+        app.snapshot.unwrap().maximum_position
+    );
+
+    app.handle_key(KeyCode::Char('c'), &mut as5600);
+    let snapshot = app.snapshot.unwrap();
+    assert_eq!(Angle::ZERO, snapshot.zero_position);
+    assert_eq!(Angle::ZERO, snapshot.maximum_position);
+
+    assert!(!app.quit);
+    app.handle_key(KeyCode::Char('q'), &mut as5600);
+    assert!(app.quit);
+}
@@ -0,0 +1,157 @@
+//! Rendering of the dashboard.
+
+use std::f64::consts::TAU;
+use std::fmt::Debug;
+
+use as5600::angle::Angle;
+use as5600::configuration::RawConfiguration;
+use as5600::snapshot::RegisterSnapshot;
+use as5600::status::Status;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::symbols::Marker;
+use ratatui::text::Line;
+use ratatui::widgets::canvas::{Canvas, Circle, Line as CanvasLine};
+use ratatui::widgets::{Block, Gauge, Paragraph};
+use ratatui::Frame;
+
+use crate::App;
+
+/// Largest value of register `MAGNITUDE`.
+const MAGNITUDE_MAX: u16 = 0x0FFF;
+
+/// Draw the dashboard.
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [main, footer] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(2)]).areas(frame.area());
+    let [dial, side] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(main);
+    let [status, agc, magnitude, config] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(3),
+        Constraint::Length(3),
+        Constraint::Min(0),
+    ])
+    .areas(side);
+
+    match &app.snapshot {
+        Some(snapshot) => {
+            draw_dial(frame, dial, snapshot);
+            draw_status(frame, status, snapshot);
+            let agc_range = app.supply.agc_range();
+            draw_gauge(
+                frame,
+                agc,
+                "AGC",
+                snapshot.automatic_gain_control.min(agc_range).into(),
+                agc_range.into(),
+            );
+            draw_gauge(
+                frame,
+                magnitude,
+                "Magnitude",
+                snapshot.magnitude,
+                MAGNITUDE_MAX,
+            );
+            draw_config(frame, config, &snapshot.raw_config());
+        }
+        None => frame.render_widget(
+            Paragraph::new("Waiting for the first read...").block(Block::bordered()),
+            main,
+        ),
+    }
+
+    let footer_text = vec![Line::from(app.message.as_str()), Line::from(App::HELP)];
+    frame.render_widget(Paragraph::new(footer_text), footer);
+}
+
+/// Point on the unit circle for an angle, clockwise from the top.
+fn dial_point(angle: Angle, radius: f64) -> (f64, f64) {
+    let turns = f64::from(angle.counts()) / f64::from(Angle::COUNTS_PER_TURN);
+    ((turns * TAU).sin() * radius, (turns * TAU).cos() * radius)
+}
+
+fn draw_dial(frame: &mut Frame, area: Rect, snapshot: &RegisterSnapshot) {
+    let title = format!(
+        " Angle {:.1} (raw {:.1}) ",
+        snapshot.angle, snapshot.raw_angle
+    );
+    let positions = format!(
+        " ZPOS {} MPOS {} ",
+        snapshot.zero_position.counts(),
+        snapshot.maximum_position.counts()
+    );
+    let canvas = Canvas::default()
+        .block(Block::bordered().title(title).title_bottom(positions))
+        .marker(Marker::Braille)
+        .x_bounds([-1.1, 1.1])
+        .y_bounds([-1.1, 1.1])
+        .paint(|ctx| {
+            ctx.draw(&Circle {
+                x: 0.0,
+                y: 0.0,
+                radius: 1.0,
+                color: Color::Gray,
+            });
+            let (x2, y2) = dial_point(snapshot.angle, 0.8);
+            ctx.draw(&CanvasLine {
+                x1: 0.0,
+                y1: 0.0,
+                x2,
+                y2,
+                color: Color::Yellow,
+            });
+        });
+    frame.render_widget(canvas, area);
+}
+
+fn draw_status(frame: &mut Frame, area: Rect, snapshot: &RegisterSnapshot) {
+    let (text, color) = match Status::try_from(snapshot.status) {
+        Ok(status @ Status::MagnetDetected) => (format!("{status:?}"), Color::Green),
+        Ok(status @ (Status::MagnetDetectedHigh | Status::MagnetDetectedLow)) => {
+            (format!("{status:?}"), Color::Yellow)
+        }
+        Ok(status) => (format!("{status:?}"), Color::Red),
+        Err(error) => (format!("{error:?}"), Color::Red),
+    };
+    let paragraph = Paragraph::new(text)
+        .style(Style::default().fg(color))
+        .block(Block::bordered().title(" Status "));
+    frame.render_widget(paragraph, area);
+}
+
+fn draw_gauge(frame: &mut Frame, area: Rect, title: &str, value: u16, max: u16) {
+    let gauge = Gauge::default()
+        .block(Block::bordered().title(format!(" {title} ")))
+        .gauge_style(Style::default().fg(Color::Cyan))
+        .ratio(f64::from(value) / f64::from(max))
+        .label(format!("{value}/{max}"));
+    frame.render_widget(gauge, area);
+}
+
+fn field<T: Debug, E: Debug>(name: &str, value: &Result<T, E>) -> Line<'static> {
+    match value {
+        Ok(value) => Line::from(format!("{name}: {value:?}")),
+        Err(error) => Line::styled(
+            format!("{name}: {error:?}"),
+            Style::default().fg(Color::Red),
+        ),
+    }
+}
+
+fn draw_config(frame: &mut Frame, area: Rect, config: &RawConfiguration) {
+    let lines = vec![
+        field("Power mode", &config.power_mode),
+        field("Hysteresis", &config.hysteresis),
+        field("Output stage", &config.output_stage),
+        field("PWM frequency", &config.pwm_frequency),
+        field("Slow filter", &config.slow_filter),
+        field("Fast filter threshold", &config.fast_filter_threshold),
+        field("Watchdog", &config.watchdog_state),
+    ];
+    let title = format!(" Configuration 0x{:04X} ", config.bits);
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(title)),
+        area,
+    );
+}